@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use super::runner::{execute_test_suit, BlockTestError};
+use crate::statetest::find_all_json_tests;
+use structopt::StructOpt;
+
+/// Run blockchain tests.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    #[structopt(required = true)]
+    path: Vec<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), BlockTestError> {
+        for path in &self.path {
+            println!("Start running tests on: {path:?}");
+            let mut executed = 0;
+            for test_file in find_all_json_tests(path) {
+                executed += execute_test_suit(&test_file)?;
+            }
+            println!("Finished execution of {executed} tests");
+        }
+        Ok(())
+    }
+}
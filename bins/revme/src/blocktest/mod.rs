@@ -0,0 +1,6 @@
+mod cmd;
+pub mod models;
+pub mod runner;
+
+pub use cmd::Cmd;
+pub use runner::BlockTestError as Error;
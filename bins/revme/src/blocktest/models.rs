@@ -0,0 +1,88 @@
+//! Blockchain test fixture format.
+//!
+//! Follows the layout of Ethereum blockchain tests: genesis header and pre state, list of blocks
+//! and expected post state with hash of the last valid block. Transactions are signed by their
+//! Ed448 `secretKey`, same as in state tests.
+use crate::statetest::models::{deserializer::*, AccountInfo, SpecName};
+use bytes::Bytes;
+use revm::primitives::{B176, B256, U256};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub struct BlockchainTestSuite(pub BTreeMap<String, BlockchainTestUnit>);
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainTestUnit {
+    pub network: SpecName,
+    pub genesis_block_header: BlockHeader,
+    pub pre: HashMap<B176, AccountInfo>,
+    pub blocks: Vec<Block>,
+    pub post_state: HashMap<B176, AccountInfo>,
+    /// Hash of the last block that is expected to be valid.
+    pub lastblockhash: B256,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeader {
+    #[serde(deserialize_with = "deserialize_str_as_bytes")]
+    pub bloom: Bytes,
+    pub coinbase: B176,
+    #[serde(default, deserialize_with = "deserialize_str_as_u256")]
+    pub difficulty: U256,
+    #[serde(deserialize_with = "deserialize_str_as_u256")]
+    pub energy_limit: U256,
+    #[serde(deserialize_with = "deserialize_str_as_u64")]
+    pub energy_used: u64,
+    #[serde(deserialize_with = "deserialize_str_as_bytes")]
+    pub extra_data: Bytes,
+    pub hash: B256,
+    pub mix_hash: B256,
+    /// Proof of work nonce, 8 bytes.
+    #[serde(deserialize_with = "deserialize_str_as_bytes")]
+    pub nonce: Bytes,
+    #[serde(deserialize_with = "deserialize_str_as_u256")]
+    pub number: U256,
+    pub parent_hash: B256,
+    pub receipt_trie: B256,
+    pub state_root: B256,
+    #[serde(deserialize_with = "deserialize_str_as_u256")]
+    pub timestamp: U256,
+    pub transactions_trie: B256,
+    pub uncle_hash: B256,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    /// Missing if the block could not be decoded, such block is expected to be invalid.
+    pub block_header: Option<BlockHeader>,
+    #[serde(default)]
+    pub transactions: Vec<Transaction>,
+    #[serde(default)]
+    pub uncle_headers: Vec<BlockHeader>,
+    /// Reason why the block is invalid, block is expected to be valid if not set.
+    pub expect_exception: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    #[serde(deserialize_with = "deserialize_str_as_bytes")]
+    pub data: Bytes,
+    #[serde(deserialize_with = "deserialize_str_as_u64")]
+    pub energy_limit: u64,
+    #[serde(deserialize_with = "deserialize_str_as_u256")]
+    pub energy_price: U256,
+    #[serde(deserialize_with = "deserialize_str_as_u64")]
+    pub nonce: u64,
+    /// Ed448 secret key of the sender, 57 bytes.
+    #[serde(deserialize_with = "deserialize_str_as_bytes")]
+    pub secret_key: Bytes,
+    #[serde(deserialize_with = "deserialize_maybe_empty")]
+    pub to: Option<B176>,
+    #[serde(deserialize_with = "deserialize_str_as_u256")]
+    pub value: U256,
+}
@@ -0,0 +1,344 @@
+use std::path::Path;
+
+use revm::{
+    interpreter::CreateScheme,
+    primitives::{sha3, Bytecode, Bytes, Env, Network, SpecId, TransactTo, B176, B256, U256},
+    InMemoryDB,
+};
+use rlp::RlpStream;
+use thiserror::Error;
+
+use super::models::{Block, BlockHeader, BlockchainTestSuite, BlockchainTestUnit, Transaction};
+use crate::{
+    reward::{apply_rewards, block_reward},
+    statetest::{
+        caller_from_secret_key,
+        merkle_trie::{is_in_state_trie, state_trie_root, Sha3Hasher},
+        models::{AccountInfo, SpecName},
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum BlockTestError {
+    #[error("Block {number}: state root mismatched, expected: {expect:?} got: {got:?}")]
+    StateRootMismatch {
+        number: U256,
+        expect: B256,
+        got: B256,
+    },
+    #[error("Block {number}: energy used mismatched, expected: {expect} got: {got}")]
+    EnergyUsedMismatch { number: U256, expect: u64, got: u64 },
+    #[error("Block {number}: transaction {index} is invalid: {reason}")]
+    InvalidTransaction {
+        number: U256,
+        index: usize,
+        reason: String,
+    },
+    #[error("Block {number}: invalid Ed448 secret key of transaction {index}")]
+    InvalidSecretKey { number: U256, index: usize },
+    #[error("Block {number}: expected exception {exception}, but block is valid")]
+    UnexpectedValidBlock { number: U256, exception: String },
+    #[error("Block without header is expected to be invalid")]
+    MissingHeader,
+    #[error("Block {number}: header hash mismatched, expected: {expect:?} got: {got:?}")]
+    HeaderHashMismatch {
+        number: U256,
+        expect: B256,
+        got: B256,
+    },
+    #[error("Block {number}: uncle {uncle} is not one of the six previous blocks")]
+    InvalidUncle { number: U256, uncle: U256 },
+    #[error("Block {number}: parent hash mismatched, expected: {expect:?} got: {got:?}")]
+    ParentHashMismatch {
+        number: U256,
+        expect: B256,
+        got: B256,
+    },
+    #[error("Block number mismatched, expected: {expect} got: {got}")]
+    BlockNumberMismatch { expect: U256, got: U256 },
+    #[error("Last block hash mismatched, expected: {expect:?} got: {got:?}")]
+    LastBlockHashMismatch { expect: B256, got: B256 },
+    #[error("Post state of {address:?} mismatched, expected: {expect:?} got: {got:?}")]
+    PostStateMismatch {
+        address: B176,
+        expect: Box<AccountInfo>,
+        got: Box<AccountInfo>,
+    },
+    #[error("Serde json error")]
+    SerdeDeserialize(#[from] serde_json::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Execute all units of the test file, returns number of executed units.
+pub fn execute_test_suit(path: &Path) -> Result<usize, BlockTestError> {
+    let suit: BlockchainTestSuite = serde_json::from_slice(&std::fs::read(path)?)?;
+
+    let mut executed = 0;
+    for (name, unit) in suit.0 {
+        if matches!(
+            unit.network,
+            SpecName::ByzantiumToConstantinopleAt5 | SpecName::Constantinople | SpecName::Unknown
+        ) {
+            continue;
+        }
+        if let Err(error) = execute_test_unit(&unit) {
+            println!("{path:?} UNIT_TEST:{name} failed");
+            return Err(error);
+        }
+        executed += 1;
+    }
+    Ok(executed)
+}
+
+/// Import all blocks on top of genesis state and check the expected post state.
+pub fn execute_test_unit(unit: &BlockchainTestUnit) -> Result<(), BlockTestError> {
+    let mut database = InMemoryDB::default();
+    for (address, info) in unit.pre.iter() {
+        let acc_info = revm::primitives::AccountInfo {
+            balance: info.balance,
+            code_hash: sha3(&info.code),
+            code: Some(Bytecode::new_raw(info.code.clone())),
+            nonce: info.nonce,
+        };
+        database.insert_account_info(*address, acc_info);
+        for (&slot, &value) in info.storage.iter() {
+            let _ = database.insert_account_storage(*address, slot, value);
+        }
+    }
+    let genesis = &unit.genesis_block_header;
+    check_header_hash(genesis)?;
+    database.block_hashes.insert(genesis.number, genesis.hash);
+    let mut last_block_hash = genesis.hash;
+    let mut last_block_number = genesis.number;
+
+    for block in &unit.blocks {
+        // invalid block changes are dropped together with this copy.
+        let mut block_database = database.clone();
+        match (
+            import_block(
+                &mut block_database,
+                &unit.network,
+                block,
+                last_block_number,
+                last_block_hash,
+            ),
+            &block.expect_exception,
+        ) {
+            (Ok(header), None) => {
+                block_database
+                    .block_hashes
+                    .insert(header.number, header.hash);
+                last_block_hash = header.hash;
+                last_block_number = header.number;
+                database = block_database;
+            }
+            (Ok(header), Some(exception)) => {
+                return Err(BlockTestError::UnexpectedValidBlock {
+                    number: header.number,
+                    exception: exception.clone(),
+                })
+            }
+            (Err(_), Some(_)) => {}
+            (Err(error), None) => return Err(error),
+        }
+    }
+
+    if last_block_hash != unit.lastblockhash {
+        return Err(BlockTestError::LastBlockHashMismatch {
+            expect: unit.lastblockhash,
+            got: last_block_hash,
+        });
+    }
+    for (address, expect) in unit.post_state.iter() {
+        let got = database
+            .accounts
+            .get(address)
+            .and_then(|account| {
+                let info = account.info()?;
+                let code = database.contracts.get(&info.code_hash)?.original_bytes();
+                Some(AccountInfo {
+                    balance: info.balance,
+                    code,
+                    nonce: info.nonce,
+                    storage: account
+                        .storage
+                        .iter()
+                        .filter(|(_, value)| **value != U256::ZERO)
+                        .map(|(slot, value)| (*slot, *value))
+                        .collect(),
+                })
+            })
+            .unwrap_or_else(|| AccountInfo {
+                balance: U256::ZERO,
+                code: Bytes::new(),
+                nonce: 0,
+                storage: Default::default(),
+            });
+        let mut expect = expect.clone();
+        expect.storage.retain(|_, value| *value != U256::ZERO);
+        if got != expect {
+            return Err(BlockTestError::PostStateMismatch {
+                address: *address,
+                expect: Box::new(expect),
+                got: Box::new(got),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check that block extends the parent, execute its transactions, reward the miner and check
+/// resulting header fields.
+fn import_block<'a>(
+    database: &mut InMemoryDB,
+    network: &SpecName,
+    block: &'a Block,
+    parent_number: U256,
+    parent_hash: B256,
+) -> Result<&'a BlockHeader, BlockTestError> {
+    let header = block
+        .block_header
+        .as_ref()
+        .ok_or(BlockTestError::MissingHeader)?;
+    if header.number != parent_number + U256::from(1) {
+        return Err(BlockTestError::BlockNumberMismatch {
+            expect: parent_number + U256::from(1),
+            got: header.number,
+        });
+    }
+    if header.parent_hash != parent_hash {
+        return Err(BlockTestError::ParentHashMismatch {
+            number: header.number,
+            expect: parent_hash,
+            got: header.parent_hash,
+        });
+    }
+    check_header_hash(header)?;
+    let spec_id = network.to_spec_id_at(header.number);
+
+    let mut env = Env::default();
+    env.cfg.network_id = 1;
+    env.cfg.spec_id = spec_id;
+    env.block.number = header.number;
+    env.block.coinbase = header.coinbase;
+    env.block.timestamp = header.timestamp;
+    env.block.energy_limit = header.energy_limit;
+    env.block.difficulty = header.difficulty;
+
+    let mut energy_used = 0u64;
+    for (index, tx) in block.transactions.iter().enumerate() {
+        if U256::from(energy_used.saturating_add(tx.energy_limit)) > header.energy_limit {
+            return Err(BlockTestError::InvalidTransaction {
+                number: header.number,
+                index,
+                reason: "block energy limit reached".to_string(),
+            });
+        }
+        fill_tx_env(&mut env, tx).ok_or(BlockTestError::InvalidSecretKey {
+            number: header.number,
+            index,
+        })?;
+
+        let mut evm = revm::new();
+        evm.database(&mut *database);
+        evm.env = env.clone();
+        let result = evm
+            .transact_commit()
+            .map_err(|error| BlockTestError::InvalidTransaction {
+                number: header.number,
+                index,
+                reason: error.to_string(),
+            })?;
+        energy_used += result.energy_used();
+    }
+
+    let uncles: Vec<_> = block
+        .uncle_headers
+        .iter()
+        .map(|uncle| (uncle.coinbase, header.number.saturating_sub(uncle.number)))
+        .collect();
+    apply_rewards(database, block_reward(spec_id), header.coinbase, &uncles).map_err(|index| {
+        BlockTestError::InvalidUncle {
+            number: header.number,
+            uncle: block.uncle_headers[index].number,
+        }
+    })?;
+
+    if energy_used != header.energy_used {
+        return Err(BlockTestError::EnergyUsedMismatch {
+            number: header.number,
+            expect: header.energy_used,
+            got: energy_used,
+        });
+    }
+    let state_root = state_root(database, spec_id);
+    if state_root != header.state_root {
+        return Err(BlockTestError::StateRootMismatch {
+            number: header.number,
+            expect: header.state_root,
+            got: state_root,
+        });
+    }
+    Ok(header)
+}
+
+/// Set transaction fields, `None` if secret key is invalid.
+fn fill_tx_env(env: &mut Env, tx: &Transaction) -> Option<()> {
+    env.tx.caller = caller_from_secret_key(&tx.secret_key, Network::from(env.cfg.network_id))?;
+    env.tx.energy_limit = tx.energy_limit;
+    env.tx.energy_price = tx.energy_price;
+    env.tx.nonce = Some(tx.nonce);
+    env.tx.data = tx.data.clone();
+    env.tx.value = tx.value;
+    env.tx.transact_to = match tx.to {
+        Some(address) => TransactTo::Call(address),
+        None => TransactTo::Create(CreateScheme::Create),
+    };
+    Some(())
+}
+
+/// Check that header hash is the SHA3-256 hash of the RLP encoded header.
+fn check_header_hash(header: &BlockHeader) -> Result<(), BlockTestError> {
+    let hash = header_hash(header);
+    if hash != header.hash {
+        return Err(BlockTestError::HeaderHashMismatch {
+            number: header.number,
+            expect: header.hash,
+            got: hash,
+        });
+    }
+    Ok(())
+}
+
+/// Hash of the header fields in the order of the go-core header.
+fn header_hash(header: &BlockHeader) -> B256 {
+    let mut stream = RlpStream::new_list(15);
+    stream.append(&header.parent_hash.0.as_ref());
+    stream.append(&header.uncle_hash.0.as_ref());
+    stream.append(&header.coinbase.0.as_ref());
+    stream.append(&header.state_root.0.as_ref());
+    stream.append(&header.transactions_trie.0.as_ref());
+    stream.append(&header.receipt_trie.0.as_ref());
+    stream.append(&header.bloom.as_ref());
+    stream.append(&header.difficulty);
+    stream.append(&header.number);
+    stream.append(&header.energy_limit);
+    stream.append(&header.energy_used);
+    stream.append(&header.timestamp);
+    stream.append(&header.extra_data.as_ref());
+    stream.append(&header.mix_hash.0.as_ref());
+    stream.append(&header.nonce.as_ref());
+    sha3(&stream.out())
+}
+
+/// State root of the Core state trie, hashed with SHA3-256.
+fn state_root(database: &InMemoryDB, spec_id: SpecId) -> B256 {
+    state_trie_root::<Sha3Hasher>(
+        database
+            .accounts
+            .iter()
+            .filter(|(_address, acc)| is_in_state_trie(spec_id, acc))
+            .map(|(k, v)| (*k, v.clone())),
+    )
+}
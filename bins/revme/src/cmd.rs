@@ -1,4 +1,4 @@
-use crate::{runner, statetest};
+use crate::{blocktest, filltest, runner, statetest, t8n, tracediff};
 use structopt::{clap::AppSettings, StructOpt};
 
 #[derive(StructOpt, Debug)]
@@ -6,15 +6,28 @@ use structopt::{clap::AppSettings, StructOpt};
 #[allow(clippy::large_enum_variant)]
 pub enum MainCmd {
     Statetest(statetest::Cmd),
+    Filltest(filltest::Cmd),
+    Blocktest(blocktest::Cmd),
+    T8n(t8n::Cmd),
     Run(runner::Cmd),
+    Tracediff(tracediff::Cmd),
 }
 
 use thiserror::Error as ThisError;
 
 #[derive(Debug, ThisError)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     #[error("Statetest: {0}")]
     Statetest(statetest::Error),
+    #[error("Filltest: {0}")]
+    Filltest(filltest::Error),
+    #[error("Blocktest: {0}")]
+    Blocktest(blocktest::Error),
+    #[error("T8n: {0}")]
+    T8n(t8n::Error),
+    #[error("Tracediff: {0}")]
+    Tracediff(tracediff::Error),
     #[error("Generic system error")]
     SystemError,
 }
@@ -23,6 +36,10 @@ impl MainCmd {
     pub fn run(&self) -> Result<(), Error> {
         match self {
             Self::Statetest(cmd) => cmd.run().map_err(Error::Statetest),
+            Self::Filltest(cmd) => cmd.run().map_err(Error::Filltest),
+            Self::Blocktest(cmd) => cmd.run().map_err(Error::Blocktest),
+            Self::T8n(cmd) => cmd.run().map_err(Error::T8n),
+            Self::Tracediff(cmd) => cmd.run().map_err(Error::Tracediff),
             _ => Ok(()),
         }
     }
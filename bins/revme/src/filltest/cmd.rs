@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use super::{
+    filler::{check, fill, FillError},
+    models::CoreTestSuite,
+};
+use crate::statetest::find_all_json_tests;
+use structopt::StructOpt;
+
+/// Fill Core state test templates with expected post state, or check filled tests.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// Template files or directories with them.
+    #[structopt(required = true)]
+    path: Vec<PathBuf>,
+    /// Directory where filled tests are written, templates are overwritten if not set.
+    #[structopt(short = "o", long)]
+    output: Option<PathBuf>,
+    /// Check that filled tests match execution instead of filling them.
+    #[structopt(long)]
+    check: bool,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), FillError> {
+        for path in &self.path {
+            for test_file in find_all_json_tests(path) {
+                let mut suite: CoreTestSuite = serde_json::from_slice(&std::fs::read(&test_file)?)?;
+                for (name, unit) in suite.0.iter_mut() {
+                    if self.check {
+                        check(unit)?;
+                        println!("{test_file:?} {name}: ok");
+                    } else {
+                        fill(unit)?;
+                        println!("{test_file:?} {name}: filled");
+                    }
+                }
+                if self.check {
+                    continue;
+                }
+                let output = match &self.output {
+                    Some(dir) => {
+                        std::fs::create_dir_all(dir)?;
+                        dir.join(test_file.file_name().unwrap())
+                    }
+                    None => test_file,
+                };
+                std::fs::write(output, serde_json::to_string_pretty(&suite)?)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use super::models::{CoreTestUnit, PostState, TxIndexes};
+use crate::statetest::{
+    caller_from_secret_key,
+    merkle_trie::{is_in_state_trie, log_rlp_hash, state_trie_root, Sha3Hasher},
+};
+use revm::{
+    interpreter::CreateScheme,
+    primitives::{AccountInfo, Bytecode, Env, ExecutionResult, Network, SpecId, TransactTo},
+    InMemoryDB,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FillError {
+    #[error("Invalid Ed448 secret key, expected 57 bytes")]
+    InvalidSecretKey,
+    #[error("Transaction is invalid: {0}")]
+    Transaction(String),
+    #[error("{spec_id:?} {indexes:?}: expected {expected:?} got {got:?}")]
+    Mismatch {
+        spec_id: SpecId,
+        indexes: TxIndexes,
+        expected: Box<PostState>,
+        got: Box<PostState>,
+    },
+    #[error("{spec_id:?} {indexes:?}: not filled")]
+    NotFilled { spec_id: SpecId, indexes: TxIndexes },
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serde json error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Pre state of the test.
+pub fn pre_state(unit: &CoreTestUnit) -> InMemoryDB {
+    let mut database = InMemoryDB::default();
+    for (address, account) in &unit.pre {
+        let code = Bytecode::new_raw(account.code.clone());
+        database.insert_account_info(
+            *address,
+            AccountInfo::new(account.balance, account.nonce, code),
+        );
+        for (&slot, &value) in &account.storage {
+            let _ = database.insert_account_storage(*address, slot, value);
+        }
+    }
+    database
+}
+
+/// Execute the transaction selected by `indexes` on pre state and return resulting [PostState].
+pub fn execute(
+    unit: &CoreTestUnit,
+    spec_id: SpecId,
+    indexes: TxIndexes,
+) -> Result<(PostState, ExecutionResult), FillError> {
+    let network = Network::from(unit.env.network_id);
+    let mut env = Env::default();
+    env.cfg.network_id = unit.env.network_id;
+    env.cfg.spec_id = spec_id;
+
+    env.block.number = unit.env.number;
+    env.block.coinbase = unit.env.coinbase;
+    env.block.timestamp = unit.env.timestamp;
+    env.block.energy_limit = unit.env.energy_limit;
+    env.block.difficulty = unit.env.difficulty;
+
+    let tx = &unit.transaction;
+    env.tx.caller =
+        caller_from_secret_key(&tx.secret_key, network).ok_or(FillError::InvalidSecretKey)?;
+    env.tx.energy_price = tx.energy_price;
+    env.tx.data = tx.data[indexes.data].clone();
+    env.tx.energy_limit = tx.energy_limit[indexes.energy];
+    env.tx.value = tx.value[indexes.value];
+    env.tx.transact_to = match tx.to {
+        Some(address) => TransactTo::Call(address),
+        None => TransactTo::Create(CreateScheme::Create),
+    };
+
+    let mut evm = revm::new();
+    evm.env = env;
+    evm.database(pre_state(unit));
+    let result = evm
+        .transact_commit()
+        .map_err(|error| FillError::Transaction(error.to_string()))?;
+
+    let db = evm.take_db();
+    let hash = state_trie_root::<Sha3Hasher>(
+        db.accounts
+            .into_iter()
+            .filter(|(_address, acc)| is_in_state_trie(spec_id, acc)),
+    );
+    let post = PostState {
+        indexes,
+        hash,
+        logs: log_rlp_hash(result.logs()),
+        energy_used: result.energy_used(),
+    };
+    Ok((post, result))
+}
+
+/// Execute every fork and index combination and replace `post` with the results.
+pub fn fill(unit: &mut CoreTestUnit) -> Result<(), FillError> {
+    let mut post = std::collections::BTreeMap::new();
+    for &spec_id in &unit.forks {
+        let mut states = Vec::new();
+        for indexes in unit.transaction.indexes() {
+            states.push(execute(unit, spec_id, indexes)?.0);
+        }
+        post.insert(spec_id, states);
+    }
+    unit.post = post;
+    Ok(())
+}
+
+/// Execute every fork and index combination and compare results with `post`.
+pub fn check(unit: &CoreTestUnit) -> Result<(), FillError> {
+    for &spec_id in &unit.forks {
+        for indexes in unit.transaction.indexes() {
+            let expected = unit
+                .post
+                .get(&spec_id)
+                .and_then(|states| states.iter().find(|state| state.indexes == indexes))
+                .ok_or(FillError::NotFilled { spec_id, indexes })?;
+            let (got, _) = execute(unit, spec_id, indexes)?;
+            if *expected != got {
+                return Err(FillError::Mismatch {
+                    spec_id,
+                    indexes,
+                    expected: Box::new(expected.clone()),
+                    got: Box::new(got),
+                });
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,6 @@
+mod cmd;
+pub mod filler;
+pub mod models;
+
+pub use cmd::Cmd;
+pub use filler::FillError as Error;
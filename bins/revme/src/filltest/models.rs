@@ -0,0 +1,131 @@
+//! Core state test fixture format.
+//!
+//! Same fixture is used as a template and as a filled test. Template lists `forks` to fill and
+//! filling adds `post` with expected SHA3-256 state root and logs hash for every fork and every
+//! combination of transaction `data`, `energyLimit` and `value`.
+use bytes::Bytes;
+use revm::primitives::{SpecId, B176, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoreTestSuite(pub BTreeMap<String, CoreTestUnit>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoreTestUnit {
+    pub env: CoreEnv,
+    pub pre: BTreeMap<B176, CoreAccount>,
+    pub transaction: CoreTransaction,
+    /// CVM specs the test is filled for.
+    pub forks: Vec<SpecId>,
+    /// Expected results, filled by `revme filltest`.
+    #[serde(default)]
+    pub post: BTreeMap<SpecId, Vec<PostState>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CoreEnv {
+    /// Network id used for ICAN addresses, mainnet by default.
+    #[serde(default = "default_network_id")]
+    pub network_id: u64,
+    pub coinbase: B176,
+    pub number: U256,
+    pub timestamp: U256,
+    pub energy_limit: U256,
+    #[serde(default)]
+    pub difficulty: U256,
+}
+
+fn default_network_id() -> u64 {
+    1
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoreAccount {
+    pub balance: U256,
+    #[serde(default, with = "revm::primitives::utilities::serde_hex_bytes")]
+    pub code: Bytes,
+    #[serde(default)]
+    pub nonce: u64,
+    #[serde(default)]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CoreTransaction {
+    #[serde(with = "hex_bytes_vec")]
+    pub data: Vec<Bytes>,
+    pub energy_limit: Vec<u64>,
+    #[serde(default)]
+    pub energy_price: U256,
+    /// Ed448 secret key of the sender, 57 bytes.
+    #[serde(with = "revm::primitives::utilities::serde_hex_bytes")]
+    pub secret_key: Bytes,
+    /// Contract creation if not set.
+    #[serde(default)]
+    pub to: Option<B176>,
+    pub value: Vec<U256>,
+}
+
+/// Indexes into [CoreTransaction] `data`, `energy_limit` and `value`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TxIndexes {
+    pub data: usize,
+    pub energy: usize,
+    pub value: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PostState {
+    pub indexes: TxIndexes,
+    /// SHA3-256 state trie root.
+    pub hash: B256,
+    /// SHA3-256 of RLP encoded logs.
+    pub logs: B256,
+    pub energy_used: u64,
+}
+
+impl CoreTransaction {
+    /// All combinations of indexes.
+    pub fn indexes(&self) -> Vec<TxIndexes> {
+        let mut indexes = Vec::new();
+        for data in 0..self.data.len() {
+            for energy in 0..self.energy_limit.len() {
+                for value in 0..self.value.len() {
+                    indexes.push(TxIndexes {
+                        data,
+                        energy,
+                        value,
+                    });
+                }
+            }
+        }
+        indexes
+    }
+}
+
+mod hex_bytes_vec {
+    use bytes::Bytes;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(items: &[Bytes], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(items.iter().map(|item| format!("0x{}", hex::encode(item))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Bytes>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .into_iter()
+            .map(|item| {
+                hex::decode(item.strip_prefix("0x").unwrap_or(&item))
+                    .map(Into::into)
+                    .map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
@@ -1 +1,6 @@
+pub mod blocktest;
+pub mod filltest;
+pub mod reward;
 pub mod statetest;
+pub mod t8n;
+pub mod tracediff;
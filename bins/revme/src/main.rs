@@ -1,7 +1,12 @@
+mod blocktest;
 mod cmd;
 mod exec;
+mod filltest;
+mod reward;
 mod runner;
 mod statetest;
+mod t8n;
+mod tracediff;
 use cmd::Error;
 use structopt::StructOpt;
 mod cli_env;
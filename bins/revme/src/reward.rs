@@ -0,0 +1,99 @@
+//! Miner and uncle rewards, shared by blockchain tests and the state transition tool.
+use revm::{
+    db::AccountState,
+    primitives::{SpecId, B176, U256},
+    InMemoryDB,
+};
+
+/// Maximum distance between block and its uncle.
+pub const MAX_UNCLE_DEPTH: u64 = 6;
+
+/// Reward of the block miner, without fees and uncle inclusion rewards.
+pub fn block_reward(spec_id: SpecId) -> U256 {
+    let reward: u64 = if SpecId::enabled(spec_id, SpecId::PETERSBURG) {
+        2_000_000_000_000_000_000
+    } else if SpecId::enabled(spec_id, SpecId::BYZANTIUM) {
+        3_000_000_000_000_000_000
+    } else {
+        5_000_000_000_000_000_000
+    };
+    U256::from(reward)
+}
+
+/// Reward of the uncle miner, `None` if uncle is not one of the [`MAX_UNCLE_DEPTH`] blocks
+/// before the block. `depth` is block number minus uncle number.
+pub fn uncle_reward(reward: U256, depth: U256) -> Option<U256> {
+    if depth == U256::ZERO || depth > U256::from(MAX_UNCLE_DEPTH) {
+        return None;
+    }
+    Some(reward * (U256::from(8) - depth) / U256::from(8))
+}
+
+/// Reward `coinbase` for the block and for each included uncle, and reward miners of `uncles`,
+/// given by their coinbase and depth.
+///
+/// Returns index of the first invalid uncle, nothing is rewarded in that case.
+pub fn apply_rewards(
+    database: &mut InMemoryDB,
+    reward: U256,
+    coinbase: B176,
+    uncles: &[(B176, U256)],
+) -> Result<(), usize> {
+    let uncle_rewards = uncles
+        .iter()
+        .enumerate()
+        .map(|(index, (_, depth))| uncle_reward(reward, *depth).ok_or(index))
+        .collect::<Result<Vec<_>, _>>()?;
+    add_balance(
+        database,
+        coinbase,
+        reward + reward / U256::from(32) * U256::from(uncles.len()),
+    );
+    for ((address, _), uncle_reward) in uncles.iter().zip(uncle_rewards) {
+        add_balance(database, *address, uncle_reward);
+    }
+    Ok(())
+}
+
+/// Add `amount` to the balance of `address`, account that does not exist becomes touched.
+pub fn add_balance(database: &mut InMemoryDB, address: B176, amount: U256) {
+    let account = database.load_account(address).unwrap();
+    if matches!(account.account_state, AccountState::NotExisting) {
+        account.account_state = AccountState::Touched;
+    }
+    account.info.balance += amount;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uncle_reward() {
+        let reward = U256::from(8_000);
+        assert_eq!(uncle_reward(reward, U256::from(1)), Some(U256::from(7_000)));
+        assert_eq!(uncle_reward(reward, U256::from(6)), Some(U256::from(2_000)));
+        assert_eq!(uncle_reward(reward, U256::ZERO), None);
+        assert_eq!(uncle_reward(reward, U256::from(7)), None);
+        assert_eq!(uncle_reward(reward, U256::MAX), None);
+    }
+
+    #[test]
+    fn test_apply_rewards() {
+        let (coinbase, uncle) = (B176::repeat_byte(1), B176::repeat_byte(2));
+        let reward = U256::from(3_200);
+
+        let mut database = InMemoryDB::default();
+        let invalid = [(uncle, U256::from(1)), (uncle, U256::from(7))];
+        assert_eq!(
+            apply_rewards(&mut database, reward, coinbase, &invalid),
+            Err(1)
+        );
+        assert!(database.accounts.is_empty());
+
+        let uncles = [(uncle, U256::from(2))];
+        apply_rewards(&mut database, reward, coinbase, &uncles).unwrap();
+        assert_eq!(database.accounts[&coinbase].info.balance, U256::from(3_300));
+        assert_eq!(database.accounts[&uncle].info.balance, U256::from(2_400));
+    }
+}
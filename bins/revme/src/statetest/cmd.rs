@@ -1,6 +1,11 @@
 use std::path::PathBuf;
 
-use super::runner::{find_all_json_tests, run, TestError};
+use super::{
+    filter::TestFilter,
+    models::SpecName,
+    runner::{find_all_json_tests, run, TestError},
+};
+use regex::Regex;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -11,14 +16,84 @@ pub struct Cmd {
     single_thread: bool,
     #[structopt(long)]
     json: bool,
+    /// Include memory in EIP-3155 trace lines.
+    #[structopt(long)]
+    trace_memory: bool,
+    /// Include return data in EIP-3155 trace lines.
+    #[structopt(long)]
+    trace_return_data: bool,
+    /// Run only tests with name matching the regex.
+    #[structopt(long)]
+    name: Option<Regex>,
+    /// Run only tests of these specs, for example `Istanbul`.
+    #[structopt(long = "spec", parse(try_from_str = parse_spec_name))]
+    specs: Vec<SpecName>,
+    /// File with names of skipped test files and directories, one per line. Replaces the
+    /// default skip list.
+    #[structopt(long)]
+    skip_list: Option<PathBuf>,
+    /// Run all tests instead of stopping on the first failure.
+    #[structopt(short = "k", long)]
+    keep_going: bool,
+    /// Write JSON report of all executed tests.
+    #[structopt(long)]
+    report_json: Option<PathBuf>,
+    /// Write JUnit XML report of all executed tests.
+    #[structopt(long)]
+    report_junit: Option<PathBuf>,
+}
+
+fn parse_spec_name(name: &str) -> Result<SpecName, String> {
+    match serde_json::from_value(name.into()) {
+        Ok(SpecName::Unknown) | Err(_) => Err(format!("unknown spec {name}")),
+        Ok(spec_name) => Ok(spec_name),
+    }
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), TestError> {
+        let mut filter = TestFilter {
+            name: self.name.clone(),
+            specs: self.specs.clone(),
+            ..Default::default()
+        };
+        if let Some(skip_list) = &self.skip_list {
+            let skip_list =
+                std::fs::read_to_string(skip_list).map_err(|_| TestError::SystemError)?;
+            filter.skip = TestFilter::parse_skip_list(&skip_list);
+        }
+
+        let mut test_files = Vec::new();
         for path in &self.path {
             println!("Start running tests on: {path:?}");
-            let test_files = find_all_json_tests(path);
-            run(test_files, self.single_thread, self.json)?
+            test_files.extend(find_all_json_tests(path));
+        }
+        let report = run(
+            test_files,
+            self.single_thread,
+            self.json,
+            self.trace_memory,
+            self.trace_return_data,
+            filter,
+            self.keep_going,
+        )?;
+        println!("Passed: {}, failed: {}", report.passed, report.failed);
+
+        if let Some(path) = &self.report_json {
+            report
+                .write_json(path)
+                .map_err(|_| TestError::SystemError)?;
+        }
+        if let Some(path) = &self.report_junit {
+            report
+                .write_junit(path)
+                .map_err(|_| TestError::SystemError)?;
+        }
+        if report.failed > 0 {
+            return Err(TestError::Failed {
+                failed: report.failed,
+                total: report.tests.len(),
+            });
         }
         Ok(())
     }
@@ -3,11 +3,11 @@ use hash_db::Hasher;
 use plain_hasher::PlainHasher;
 use primitive_types::H256;
 use revm::{
-    db::DbAccount,
-    primitives::{sha3, Log, B176, B256, U256},
+    db::{AccountState, DbAccount},
+    primitives::{sha3, Log, SpecId, B176, B256, U256},
 };
 use rlp::RlpStream;
-use sha3::{Digest, Keccak256};
+use sha3::{Digest, Keccak256, Sha3_256};
 use triehash::sec_trie_root;
 
 pub fn log_rlp_hash(logs: Vec<Log>) -> B256 {
@@ -30,6 +30,16 @@ pub fn log_rlp_hash(logs: Vec<Log>) -> B256 {
     sha3(&out)
 }
 
+/// Returns true if account is part of the post state trie. Accounts that don't exist are never
+/// part of it, since EIP-161 empty accounts are left out too unless they were not changed.
+pub fn is_in_state_trie(spec_id: SpecId, acc: &DbAccount) -> bool {
+    if SpecId::enabled(spec_id, SpecId::SPURIOUS_DRAGON) {
+        !acc.info.is_empty() || matches!(acc.account_state, AccountState::None)
+    } else {
+        !matches!(acc.account_state, AccountState::NotExisting)
+    }
+}
+
 pub fn state_merkle_trie_root(accounts: impl Iterator<Item = (B176, DbAccount)>) -> B256 {
     let vec = accounts
         .map(|(address, info)| {
@@ -41,13 +51,32 @@ pub fn state_merkle_trie_root(accounts: impl Iterator<Item = (B176, DbAccount)>)
     trie_root(vec)
 }
 
+/// State root of a trie that uses `H` for hashing keys and nodes.
+pub fn state_trie_root<H: Hasher<Out = H256>>(
+    accounts: impl Iterator<Item = (B176, DbAccount)>,
+) -> B256 {
+    let vec: Vec<_> = accounts
+        .map(|(address, info)| {
+            let acc_root = trie_account_rlp_with::<H>(&info);
+            (B176::from(address.0), acc_root)
+        })
+        .collect();
+
+    B256(sec_trie_root::<H, _, _, _>(vec).0)
+}
+
 /// Returns the RLP for this account.
 pub fn trie_account_rlp(acc: &DbAccount) -> Bytes {
+    trie_account_rlp_with::<KeccakHasher>(acc)
+}
+
+/// Returns the RLP for this account, with storage root hashed by `H`.
+pub fn trie_account_rlp_with<H: Hasher<Out = H256>>(acc: &DbAccount) -> Bytes {
     let mut stream = RlpStream::new_list(4);
     stream.append(&acc.info.nonce);
     stream.append(&acc.info.balance);
     stream.append(&{
-        sec_trie_root::<KeccakHasher, _, _, _>(
+        sec_trie_root::<H, _, _, _>(
             acc.storage
                 .iter()
                 .filter(|(_k, &v)| v != U256::ZERO)
@@ -74,3 +103,17 @@ impl Hasher for KeccakHasher {
         H256::from_slice(out.as_slice())
     }
 }
+
+/// Hasher of Core tries, SHA3-256 instead of Keccak-256.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Sha3Hasher;
+
+impl Hasher for Sha3Hasher {
+    type Out = H256;
+    type StdHasher = PlainHasher;
+    const LENGTH: usize = 32;
+    fn hash(x: &[u8]) -> Self::Out {
+        let out = Sha3_256::digest(x);
+        H256::from_slice(out.as_slice())
+    }
+}
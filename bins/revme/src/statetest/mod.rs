@@ -1,7 +1,9 @@
 mod cmd;
+pub mod filter;
 pub mod merkle_trie;
 pub mod models;
+pub mod report;
 mod runner;
 
 pub use cmd::Cmd;
-pub use runner::TestError as Error;
+pub use runner::{caller_from_secret_key, find_all_json_tests, TestError as Error};
@@ -1,7 +1,7 @@
 use bytes::Bytes;
 use revm::primitives::{B176, B256, U256};
 use std::collections::{BTreeMap, HashMap};
-mod deserializer;
+pub mod deserializer;
 mod spec;
 
 use deserializer::*;
@@ -78,7 +78,9 @@ pub struct TransactionParts {
     pub energy_limit: Vec<U256>,
     pub energy_price: Option<U256>,
     pub nonce: U256,
-    pub secret_key: Option<B256>,
+    /// Ed448 secret key of the sender, 57 bytes.
+    #[serde(default, deserialize_with = "deserialize_opt_str_as_bytes")]
+    pub secret_key: Option<Bytes>,
     #[serde(deserialize_with = "deserialize_maybe_empty")]
     pub to: Option<B176>,
     pub value: Vec<U256>,
@@ -1,7 +1,7 @@
-use revm::primitives::SpecId;
+use revm::primitives::{SpecId, U256};
 use serde::Deserialize;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Ord, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Hash, Ord, Deserialize)]
 pub enum SpecName {
     Frontier,
     FrontierToHomesteadAt5,
@@ -17,6 +17,9 @@ pub enum SpecName {
     Constantinople, // SKIPPED
     ConstantinopleFix,
     Istanbul,
+    CvmEd448,
+    CvmModexp,
+    CvmBls,
     #[serde(other)]
     Unknown,
 }
@@ -33,10 +36,46 @@ impl SpecName {
             Self::Byzantium | Self::EIP158ToByzantiumAt5 => SpecId::BYZANTIUM,
             Self::ConstantinopleFix | Self::ByzantiumToConstantinopleFixAt5 => SpecId::PETERSBURG,
             Self::Istanbul => SpecId::ISTANBUL,
+            Self::CvmEd448 => SpecId::CVM_ED448,
+            Self::CvmModexp => SpecId::CVM_MODEXP,
+            Self::CvmBls => SpecId::CVM_BLS,
             Self::ByzantiumToConstantinopleAt5 | Self::Constantinople => {
                 panic!("Overriden with PETERSBURG")
             }
             Self::Unknown => panic!("Unknown spec"),
         }
     }
+
+    /// Spec active at block `number`, transition specs switch at block 5.
+    pub fn to_spec_id_at(&self, number: U256) -> SpecId {
+        if number >= U256::from(5) {
+            return self.to_spec_id();
+        }
+        match self {
+            Self::FrontierToHomesteadAt5 => SpecId::FRONTIER,
+            Self::HomesteadToDaoAt5 | Self::HomesteadToEIP150At5 => SpecId::HOMESTEAD,
+            Self::EIP158ToByzantiumAt5 => SpecId::SPURIOUS_DRAGON,
+            Self::ByzantiumToConstantinopleFixAt5 => SpecId::BYZANTIUM,
+            _ => self.to_spec_id(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpecName;
+    use revm::primitives::SpecId;
+
+    #[test]
+    fn test_cvm_fork_names() {
+        for (name, spec_id) in [
+            ("CvmEd448", SpecId::CVM_ED448),
+            ("CvmModexp", SpecId::CVM_MODEXP),
+            ("CvmBls", SpecId::CVM_BLS),
+        ] {
+            let spec_name: SpecName = serde_json::from_value(name.into()).unwrap();
+            assert_eq!(spec_name.to_spec_id(), spec_id);
+            assert_eq!(SpecId::from(name), spec_id);
+        }
+    }
 }
@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+/// Result of a single state test, one spec and one set of transaction indexes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestOutcome {
+    pub file: String,
+    pub name: String,
+    pub spec: String,
+    pub index: usize,
+    pub passed: bool,
+    pub energy_used: u64,
+    pub failure: Option<String>,
+    #[serde(serialize_with = "serialize_secs")]
+    pub time: Duration,
+}
+
+/// Outcomes of all executed state tests.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestReport {
+    pub passed: usize,
+    pub failed: usize,
+    #[serde(serialize_with = "serialize_secs")]
+    pub time: Duration,
+    pub tests: Vec<TestOutcome>,
+}
+
+fn serialize_secs<S: Serializer>(time: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(time.as_secs_f64())
+}
+
+impl TestReport {
+    pub fn new(mut tests: Vec<TestOutcome>, time: Duration) -> Self {
+        tests.sort_by(|a, b| {
+            (&a.file, &a.name, &a.spec, a.index).cmp(&(&b.file, &b.name, &b.spec, b.index))
+        });
+        let failed = tests.iter().filter(|test| !test.passed).count();
+        Self {
+            passed: tests.len() - failed,
+            failed,
+            time,
+            tests,
+        }
+    }
+
+    pub fn write_json(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn write_junit(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_junit())
+    }
+
+    /// JUnit XML with one test suite per test file.
+    pub fn to_junit(&self) -> String {
+        let mut suites: BTreeMap<&str, Vec<&TestOutcome>> = BTreeMap::new();
+        for test in &self.tests {
+            suites.entry(&test.file).or_default().push(test);
+        }
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"statetest\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">",
+            self.tests.len(),
+            self.failed,
+            self.time.as_secs_f64()
+        );
+        for (file, tests) in suites {
+            let failures = tests.iter().filter(|test| !test.passed).count();
+            let time: Duration = tests.iter().map(|test| test.time).sum();
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" time=\"{:.6}\">",
+                escape(file),
+                tests.len(),
+                time.as_secs_f64()
+            );
+            for test in tests {
+                let _ = write!(
+                    xml,
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\">",
+                    escape(file),
+                    escape(&format!("{} {} #{}", test.name, test.spec, test.index)),
+                    test.time.as_secs_f64()
+                );
+                let _ = write!(
+                    xml,
+                    "<system-out>energy used: {}</system-out>",
+                    test.energy_used
+                );
+                if let Some(failure) = &test.failure {
+                    let _ = write!(xml, "<failure message=\"{}\"/>", escape(failure));
+                }
+                xml.push_str("</testcase>\n");
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Escape text for XML attributes and content.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(name: &str, failure: Option<&str>) -> TestOutcome {
+        TestOutcome {
+            file: "stExample/add11.json".to_string(),
+            name: name.to_string(),
+            spec: "Istanbul".to_string(),
+            index: 0,
+            passed: failure.is_none(),
+            energy_used: 43112,
+            failure: failure.map(str::to_string),
+            time: Duration::from_millis(2),
+        }
+    }
+
+    #[test]
+    fn test_reports() {
+        let report = TestReport::new(
+            vec![
+                outcome("b", Some("root <mismatched> & \"wrong\"")),
+                outcome("a", None),
+            ],
+            Duration::from_millis(4),
+        );
+        assert_eq!((report.passed, report.failed), (1, 1));
+        assert_eq!(report.tests[0].name, "a");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["tests"][0]["energyUsed"], 43112);
+        assert_eq!(json["tests"][1]["passed"], false);
+
+        let junit = report.to_junit();
+        assert!(junit.contains("<testsuites name=\"statetest\" tests=\"2\" failures=\"1\""));
+        assert!(junit.contains("name=\"a Istanbul #0\""));
+        assert!(junit
+            .contains("<failure message=\"root &lt;mismatched&gt; &amp; &quot;wrong&quot;\"/>"));
+    }
+}
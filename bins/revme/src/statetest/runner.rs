@@ -1,7 +1,5 @@
 use std::io::stdout;
 use std::{
-    collections::HashMap,
-    ffi::OsStr,
     path::{Path, PathBuf},
     sync::{atomic::AtomicBool, Arc, Mutex},
     time::{Duration, Instant},
@@ -11,18 +9,22 @@ use indicatif::ProgressBar;
 
 use revm::inspectors::TracerEip3155;
 use revm::{
-    db::AccountState,
     interpreter::CreateScheme,
-    primitives::{Bytecode, Env, ExecutionResult, SpecId, TransactTo, B176, B256, U256},
+    primitives::{
+        pubkey_to_address, Bytecode, Bytes, Env, ExecutionResult, Network, SpecId, TransactTo,
+        B176, B256, U256,
+    },
 };
 use std::sync::atomic::Ordering;
 use walkdir::{DirEntry, WalkDir};
 
 use super::{
-    merkle_trie::{log_rlp_hash, state_merkle_trie_root},
+    filter::TestFilter,
+    merkle_trie::{is_in_state_trie, log_rlp_hash, state_merkle_trie_root},
     models::{SpecName, TestSuit},
+    report::{TestOutcome, TestReport},
 };
-use hex_literal::hex;
+use libgoldilocks::goldilocks::ed448_derive_public;
 use revm::primitives::sha3;
 use thiserror::Error;
 
@@ -39,8 +41,17 @@ pub enum TestError {
     SerdeDeserialize(#[from] serde_json::Error),
     #[error("Internal system error")]
     SystemError,
-    #[error("Unknown private key: {private_key:?}")]
-    UnknownPrivateKey { private_key: B256 },
+    #[error("Invalid Ed448 secret key: {secret_key:?}")]
+    InvalidSecretKey { secret_key: Option<Bytes> },
+    #[error("{failed} of {total} tests failed")]
+    Failed { failed: usize, total: usize },
+}
+
+/// Derive the transaction sender from its Ed448 secret key, `None` if key is not 57 bytes long.
+pub fn caller_from_secret_key(secret_key: &[u8], network: Network) -> Option<B176> {
+    let secret: [u8; 57] = secret_key.try_into().ok()?;
+    let public = ed448_derive_public(&secret);
+    Some(pubkey_to_address(&public, network))
 }
 
 pub fn find_all_json_tests(path: &Path) -> Vec<PathBuf> {
@@ -52,106 +63,29 @@ pub fn find_all_json_tests(path: &Path) -> Vec<PathBuf> {
         .collect::<Vec<PathBuf>>()
 }
 
+/// Execute tests of the file selected by `filter`. Unless `keep_going` is set, execution stops on
+/// the first failed test.
 pub fn execute_test_suit(
     path: &Path,
     elapsed: &Arc<Mutex<Duration>>,
+    filter: &TestFilter,
+    keep_going: bool,
     trace: bool,
-) -> Result<(), TestError> {
-    // funky test with `bigint 0x00` value in json :) not possible to happen on mainnet and require custom json parser.
-    // https://github.com/ethereum/tests/issues/971
-    if path.file_name() == Some(OsStr::new("ValueOverflow.json")) {
-        return Ok(());
-    }
-    // txbyte is of type 02 and we dont parse tx bytes for this test to fail.
-    if path.file_name() == Some(OsStr::new("typeTwoBerlin.json")) {
-        return Ok(());
-    }
-    // Test checks if nonce overflows. We are handling this correctly but we are not parsing exception in testsuite
-    // There are more nonce overflow tests that are in internal call/create, and those tests are passing and are enabled.
-    if path.file_name() == Some(OsStr::new("CreateTransactionHighNonce.json")) {
-        return Ok(());
-    }
-
-    // Need to handle Test errors
-    if path.file_name() == Some(OsStr::new("transactionIntinsicBug.json")) {
-        return Ok(());
-    }
-
-    // Test check if energy price overflows, we handle this correctly but does not match tests specific exception.
-    if path.file_name() == Some(OsStr::new("HighEnergyPrice.json")) {
-        return Ok(());
-    }
-
-    // Skip test where basefee/accesslist/diffuculty is present but it shouldn't be supported in London/Berlin/TheMerge.
-    // https://github.com/ethereum/tests/blob/5b7e1ab3ffaf026d99d20b17bb30f533a2c80c8b/GeneralStateTests/stExample/eip1559.json#L130
-    // It is expected to not execute these tests.
-    if path.file_name() == Some(OsStr::new("accessListExample.json"))
-        || path.file_name() == Some(OsStr::new("basefeeExample.json"))
-        || path.file_name() == Some(OsStr::new("eip1559.json"))
-        || path.file_name() == Some(OsStr::new("mergeTest.json"))
-    {
-        return Ok(());
-    }
-
-    // These tests are passing, but they take a lot of time to execute so we are going to skip them.
-    if path.file_name() == Some(OsStr::new("loopExp.json"))
-        || path.file_name() == Some(OsStr::new("Call50000_sha256.json"))
-        || path.file_name() == Some(OsStr::new("static_Call50000_sha256.json"))
-        || path.file_name() == Some(OsStr::new("loopMul.json"))
-        || path.file_name() == Some(OsStr::new("CALLBlake2f_MaxRounds.json"))
-    {
-        return Ok(());
-    }
-
-    if path.to_str().unwrap().contains("stEOF") {
-        return Ok(());
+    trace_mem: bool,
+    trace_return_data: bool,
+) -> Result<Vec<TestOutcome>, TestError> {
+    let mut outcomes = Vec::new();
+    if filter.is_skipped(path) {
+        return Ok(outcomes);
     }
 
     let json_reader = std::fs::read(path).unwrap();
     let suit: TestSuit = serde_json::from_reader(&*json_reader)?;
 
-    let map_caller_keys: HashMap<_, _> = vec![
-        (
-            B256(hex!(
-                "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
-            )),
-            B176(hex!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b0000")),
-        ),
-        (
-            B256(hex!(
-                "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4"
-            )),
-            B176(hex!("cd2a3d9f938e13cd947ec05abc7fe734df8dd8260000")),
-        ),
-        (
-            B256(hex!(
-                "044852b2a670ade5407e78fb2863c51de9fcb96542a07186fe3aeda6bb8a116d"
-            )),
-            B176(hex!("82a978b3f5962a5b0957d9ee9eef472ee55b42f10000")),
-        ),
-        (
-            B256(hex!(
-                "6a7eeac5f12b409d42028f66b0b2132535ee158cfda439e3bfdd4558e8f4bf6c"
-            )),
-            B176(hex!("c9c5a15a403e41498b6f69f6f89dd9f5892d21f70000")),
-        ),
-        (
-            B256(hex!(
-                "a95defe70ebea7804f9c3be42d20d24375e2a92b9d9666b832069c5f3cd423dd"
-            )),
-            B176(hex!("3fb1cd2cd96c6d5c0b5eb3322d807b34482481d40000")),
-        ),
-        (
-            B256(hex!(
-                "fe13266ff57000135fb9aa854bbfe455d8da85b21f626307bf3263a0c2a8e7fe"
-            )),
-            B176(hex!("dcc5ba93a1ed7e045690d722f2bf460a51c614150000")),
-        ),
-    ]
-    .into_iter()
-    .collect();
-
     for (name, unit) in suit.0.into_iter() {
+        if !filter.matches_name(&name) {
+            continue;
+        }
         // Create database and insert cache
         let mut database = revm::InMemoryDB::default();
         for (address, info) in unit.pre.iter() {
@@ -180,13 +114,11 @@ pub fn execute_test_suit(
         // after the Merge prevrandao replaces mix_hash field in block and replaced difficulty opcode in EVM.
 
         //tx env
-        env.tx.caller =
-            if let Some(caller) = map_caller_keys.get(&unit.transaction.secret_key.unwrap()) {
-                *caller
-            } else {
-                let private_key = unit.transaction.secret_key.unwrap();
-                return Err(TestError::UnknownPrivateKey { private_key });
-            };
+        let secret_key = unit.transaction.secret_key.as_deref().unwrap_or_default();
+        env.tx.caller = caller_from_secret_key(secret_key, Network::from(env.cfg.network_id))
+            .ok_or_else(|| TestError::InvalidSecretKey {
+                secret_key: unit.transaction.secret_key.clone(),
+            })?;
         env.tx.energy_price = unit.transaction.energy_price.unwrap_or(U256::ZERO);
 
         // post and execution
@@ -196,7 +128,8 @@ pub fn execute_test_suit(
                 SpecName::ByzantiumToConstantinopleAt5
                     | SpecName::Constantinople
                     | SpecName::Unknown
-            ) {
+            ) || !filter.matches_spec(&spec_name)
+            {
                 continue;
             }
 
@@ -233,7 +166,11 @@ pub fn execute_test_suit(
                 let timer = Instant::now();
 
                 let exec_result = if trace {
-                    evm.inspect_commit(TracerEip3155::new(Box::new(stdout()), false, false))
+                    evm.inspect_commit(TracerEip3155::new(
+                        Box::new(stdout()),
+                        trace_mem,
+                        trace_return_data,
+                    ))
                 } else {
                     evm.transact_commit()
                 };
@@ -241,20 +178,12 @@ pub fn execute_test_suit(
 
                 *elapsed.lock().unwrap() += timer;
 
-                let is_legacy = !SpecId::enabled(
-                    evm.env.cfg.spec_id,
-                    revm::primitives::SpecId::SPURIOUS_DRAGON,
-                );
+                let spec_id = evm.env.cfg.spec_id;
                 let db = evm.db().unwrap();
                 let state_root = state_merkle_trie_root(
                     db.accounts
                         .iter()
-                        .filter(|(_address, acc)| {
-                            (is_legacy && !matches!(acc.account_state, AccountState::NotExisting))
-                                || (!is_legacy
-                                    && (!(acc.info.is_empty())
-                                        || matches!(acc.account_state, AccountState::None)))
-                        })
+                        .filter(|(_address, acc)| is_in_state_trie(spec_id, acc))
                         .map(|(k, v)| (*k, v.clone())),
                 );
                 let logs = match &exec_result {
@@ -262,81 +191,121 @@ pub fn execute_test_suit(
                     _ => Vec::new(),
                 };
                 let logs_root = log_rlp_hash(logs);
-                if test.hash != state_root || test.logs != logs_root {
-                    println!(
-                        "Roots did not match:\nState root: wanted {:?}, got {state_root:?}\nLogs root: wanted {:?}, got {logs_root:?}",
-                        test.hash, test.logs
-                    );
-                    let mut database_cloned = database.clone();
-                    evm.database(&mut database_cloned);
-                    let _ =
-                        evm.inspect_commit(TracerEip3155::new(Box::new(stdout()), false, false));
-                    let db = evm.db().unwrap();
-                    println!("{path:?} UNIT_TEST:{name}\n");
-                    match &exec_result {
-                        Ok(ExecutionResult::Success {
-                            reason,
-                            energy_used,
-                            energy_refunded,
-                            ..
-                        }) => {
-                            println!("Failed reason: {reason:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?} ({energy_refunded:?} refunded)");
-                        }
-                        Ok(ExecutionResult::Revert {
-                            energy_used,
-                            output,
-                        }) => {
-                            println!(
-                                "Reverted: {output:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?}"
-                            );
-                        }
-                        Ok(ExecutionResult::Halt {
-                            reason,
-                            energy_used,
-                        }) => {
-                            println!(
-                                "Halted: {reason:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?}"
-                            );
-                        }
-                        Err(out) => {
-                            println!("Output: {out:?} {path:?} UNIT_TEST:{name}\n");
-                        }
+                let mut outcome = TestOutcome {
+                    file: path.display().to_string(),
+                    name: name.clone(),
+                    spec: format!("{spec_name:?}"),
+                    index: id,
+                    passed: true,
+                    energy_used: exec_result.as_ref().map_or(0, ExecutionResult::energy_used),
+                    failure: None,
+                    time: timer,
+                };
+                if test.hash == state_root && test.logs == logs_root {
+                    outcomes.push(outcome);
+                    continue;
+                }
+                let error = TestError::RootMismatch {
+                    spec_id: env.cfg.spec_id,
+                    id,
+                    got: state_root,
+                    expect: test.hash,
+                };
+                outcome.passed = false;
+                outcome.failure = Some(if test.logs != logs_root {
+                    format!(
+                        "{error}, logs root expected: {:?} got: {logs_root:?}",
+                        test.logs
+                    )
+                } else {
+                    error.to_string()
+                });
+                outcomes.push(outcome);
+                if keep_going {
+                    continue;
+                }
+
+                println!(
+                    "Roots did not match:\nState root: wanted {:?}, got {state_root:?}\nLogs root: wanted {:?}, got {logs_root:?}",
+                    test.hash, test.logs
+                );
+                let mut database_cloned = database.clone();
+                evm.database(&mut database_cloned);
+                let _ = evm.inspect_commit(TracerEip3155::new(
+                    Box::new(stdout()),
+                    trace_mem,
+                    trace_return_data,
+                ));
+                let db = evm.db().unwrap();
+                println!("{path:?} UNIT_TEST:{name}\n");
+                match &exec_result {
+                    Ok(ExecutionResult::Success {
+                        reason,
+                        energy_used,
+                        energy_refunded,
+                        ..
+                    }) => {
+                        println!("Failed reason: {reason:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?} ({energy_refunded:?} refunded)");
+                    }
+                    Ok(ExecutionResult::Revert {
+                        energy_used,
+                        output,
+                    }) => {
+                        println!(
+                            "Reverted: {output:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?}"
+                        );
+                    }
+                    Ok(ExecutionResult::Halt {
+                        reason,
+                        energy_used,
+                    }) => {
+                        println!(
+                            "Halted: {reason:?} {path:?} UNIT_TEST:{name}\n energy:{energy_used:?}"
+                        );
+                    }
+                    Err(out) => {
+                        println!("Output: {out:?} {path:?} UNIT_TEST:{name}\n");
                     }
-                    println!("\nApplied state:\n{db:#?}\n");
-                    println!("\nState root: {state_root:?}\n");
-                    return Err(TestError::RootMismatch {
-                        spec_id: env.cfg.spec_id,
-                        id,
-                        got: state_root,
-                        expect: test.hash,
-                    });
                 }
+                println!("\nApplied state:\n{db:#?}\n");
+                println!("\nState root: {state_root:?}\n");
+                println!("Test named:\n{path:?} failed: {error}\n");
+                return Ok(outcomes);
             }
         }
     }
-    Ok(())
+    Ok(outcomes)
 }
 
+/// Execute tests of all files, failed tests are part of the returned report.
 pub fn run(
     test_files: Vec<PathBuf>,
     mut single_thread: bool,
     trace: bool,
-) -> Result<(), TestError> {
+    trace_mem: bool,
+    trace_return_data: bool,
+    filter: TestFilter,
+    keep_going: bool,
+) -> Result<TestReport, TestError> {
     if trace {
         single_thread = true;
     }
 
     let endjob = Arc::new(AtomicBool::new(false));
     let console_bar = Arc::new(ProgressBar::new(test_files.len() as u64));
-    let mut joins: Vec<std::thread::JoinHandle<Result<(), TestError>>> = Vec::new();
+    let mut joins: Vec<std::thread::JoinHandle<()>> = Vec::new();
     let queue = Arc::new(Mutex::new((0, test_files)));
     let elapsed = Arc::new(Mutex::new(std::time::Duration::ZERO));
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+    let filter = Arc::new(filter);
     let num_threads = if single_thread { 1 } else { 10 };
     for _ in 0..num_threads {
         let queue = queue.clone();
         let endjob = endjob.clone();
         let console_bar = console_bar.clone();
         let elapsed = elapsed.clone();
+        let outcomes = outcomes.clone();
+        let filter = filter.clone();
 
         joins.push(
             std::thread::Builder::new()
@@ -345,21 +314,42 @@ pub fn run(
                     let (index, test_path) = {
                         let mut queue = queue.lock().unwrap();
                         if queue.1.len() <= queue.0 {
-                            return Ok(());
+                            return;
                         }
                         let test_path = queue.1[queue.0].clone();
                         queue.0 += 1;
                         (queue.0 - 1, test_path)
                     };
                     if endjob.load(Ordering::SeqCst) {
-                        return Ok(());
+                        return;
                     }
                     //println!("Test:{:?}\n",test_path);
-                    if let Err(err) = execute_test_suit(&test_path, &elapsed, trace) {
-                        endjob.store(true, Ordering::SeqCst);
+                    let file_outcomes = execute_test_suit(
+                        &test_path,
+                        &elapsed,
+                        &filter,
+                        keep_going,
+                        trace,
+                        trace_mem,
+                        trace_return_data,
+                    )
+                    .unwrap_or_else(|err| {
                         println!("Test[{index}] named:\n{test_path:?} failed: {err}\n");
-                        return Err(err);
+                        vec![TestOutcome {
+                            file: test_path.display().to_string(),
+                            name: String::new(),
+                            spec: String::new(),
+                            index: 0,
+                            passed: false,
+                            energy_used: 0,
+                            failure: Some(err.to_string()),
+                            time: Duration::ZERO,
+                        }]
+                    });
+                    if !keep_going && file_outcomes.iter().any(|outcome| !outcome.passed) {
+                        endjob.store(true, Ordering::SeqCst);
                     }
+                    outcomes.lock().unwrap().extend(file_outcomes);
 
                     //println!("TestDone:{:?}\n",test_path);
                     console_bar.inc(1);
@@ -368,9 +358,11 @@ pub fn run(
         );
     }
     for handler in joins {
-        handler.join().map_err(|_| TestError::SystemError)??;
+        handler.join().map_err(|_| TestError::SystemError)?;
     }
     console_bar.finish();
-    println!("Finished execution. Time:{:?}", elapsed.lock().unwrap());
-    Ok(())
+    let elapsed = *elapsed.lock().unwrap();
+    println!("Finished execution. Time:{elapsed:?}");
+    let outcomes = std::mem::take(&mut *outcomes.lock().unwrap());
+    Ok(TestReport::new(outcomes, elapsed))
 }
@@ -0,0 +1,115 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    models::{Alloc, T8nEnv, T8nInput, T8nTransaction},
+    transition::{transition, T8nError, TransitionConfig},
+};
+use crate::statetest::models::SpecName;
+use revm::primitives::U256;
+use serde::de::DeserializeOwned;
+use structopt::StructOpt;
+
+/// Input name that reads all inputs from stdin instead of files.
+const STDIN: &str = "stdin";
+/// Output name that prints to stdout instead of writing a file.
+const STDOUT: &str = "stdout";
+
+/// State transition tool, applies transactions of one block on top of pre state.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// Pre state, `stdin` reads object with `alloc`, `env` and `txs` from stdin.
+    #[structopt(long = "input.alloc", default_value = "alloc.json")]
+    input_alloc: String,
+    /// Block environment.
+    #[structopt(long = "input.env", default_value = "env.json")]
+    input_env: String,
+    /// Transactions signed by their Ed448 `secretKey`.
+    #[structopt(long = "input.txs", default_value = "txs.json")]
+    input_txs: String,
+    /// Directory of the output files.
+    #[structopt(long = "output.basedir")]
+    output_basedir: Option<PathBuf>,
+    /// Result file or `stdout`.
+    #[structopt(long = "output.result", default_value = "result.json")]
+    output_result: String,
+    /// Post state file or `stdout`.
+    #[structopt(long = "output.alloc", default_value = "alloc.json")]
+    output_alloc: String,
+    /// Fork name as used in state tests.
+    #[structopt(long = "state.fork", default_value = "Istanbul")]
+    state_fork: String,
+    #[structopt(long = "state.networkid", default_value = "1")]
+    state_network_id: u64,
+    /// Block reward, no reward is applied if not set.
+    #[structopt(long = "state.reward")]
+    state_reward: Option<U256>,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), T8nError> {
+        let mut stdin = T8nInput::default();
+        if [&self.input_alloc, &self.input_env, &self.input_txs]
+            .iter()
+            .any(|input| input.as_str() == STDIN)
+        {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            stdin = serde_json::from_str(&input)?;
+        }
+        let alloc: Alloc = read_input(&self.input_alloc, stdin.alloc, "alloc")?;
+        let env: T8nEnv = read_input(&self.input_env, stdin.env, "env")?;
+        let txs: Vec<T8nTransaction> = read_input(&self.input_txs, stdin.txs, "txs")?;
+
+        let fork: SpecName = serde_json::from_value(self.state_fork.clone().into())?;
+        if matches!(
+            fork,
+            SpecName::ByzantiumToConstantinopleAt5 | SpecName::Constantinople | SpecName::Unknown
+        ) {
+            return Err(T8nError::UnknownFork(self.state_fork.clone()));
+        }
+        let config = TransitionConfig {
+            spec_id: fork.to_spec_id_at(env.current_number),
+            network_id: self.state_network_id,
+            reward: self.state_reward,
+        };
+        let (result, alloc) = transition(&alloc, &env, &txs, config)?;
+
+        let mut stdout = serde_json::Map::new();
+        for (name, output, value) in [
+            ("result", &self.output_result, serde_json::to_value(result)?),
+            ("alloc", &self.output_alloc, serde_json::to_value(alloc)?),
+        ] {
+            if output == STDOUT {
+                stdout.insert(name.to_string(), value);
+                continue;
+            }
+            let path = match &self.output_basedir {
+                Some(dir) => {
+                    std::fs::create_dir_all(dir)?;
+                    dir.join(output)
+                }
+                None => PathBuf::from(output),
+            };
+            std::fs::write(path, serde_json::to_string_pretty(&value)?)?;
+        }
+        if !stdout.is_empty() {
+            println!("{}", serde_json::to_string_pretty(&stdout)?);
+        }
+        Ok(())
+    }
+}
+
+/// Read input from the file at `path`, or take it from the stdin object.
+fn read_input<T: DeserializeOwned>(
+    path: &str,
+    stdin: Option<T>,
+    name: &'static str,
+) -> Result<T, T8nError> {
+    if path == STDIN {
+        return stdin.ok_or(T8nError::MissingInput(name));
+    }
+    Ok(serde_json::from_slice(&std::fs::read(Path::new(path))?)?)
+}
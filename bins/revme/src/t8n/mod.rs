@@ -0,0 +1,6 @@
+mod cmd;
+pub mod models;
+pub mod transition;
+
+pub use cmd::Cmd;
+pub use transition::T8nError as Error;
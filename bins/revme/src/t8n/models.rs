@@ -0,0 +1,155 @@
+//! Transition tool input and output formats.
+//!
+//! Field names follow the `t8n` tool of go-core, with `energy` in place of `gas`.
+use crate::statetest::models::deserializer::deserialize_opt_str_as_bytes;
+use bytes::Bytes;
+use revm::primitives::{utilities::serde_hex_bytes, Log, B176, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Accounts by address, used for pre state input and post state output.
+pub type Alloc = BTreeMap<B176, AllocAccount>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocAccount {
+    #[serde(default)]
+    pub balance: U256,
+    #[serde(
+        default,
+        skip_serializing_if = "Bytes::is_empty",
+        with = "serde_hex_bytes"
+    )]
+    pub code: Bytes,
+    #[serde(default, skip_serializing_if = "is_zero", with = "serde_hex_u64")]
+    pub nonce: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct T8nEnv {
+    pub current_coinbase: B176,
+    #[serde(default)]
+    pub current_difficulty: U256,
+    pub current_energy_limit: U256,
+    pub current_number: U256,
+    pub current_timestamp: U256,
+    /// Hashes of previous blocks by decimal or hex block number.
+    #[serde(default)]
+    pub block_hashes: BTreeMap<String, B256>,
+    #[serde(default)]
+    pub ommers: Vec<Ommer>,
+}
+
+/// Uncle of the block, `delta` is the distance from the current block number.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Ommer {
+    #[serde(with = "serde_hex_u64")]
+    pub delta: u64,
+    pub address: B176,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct T8nTransaction {
+    #[serde(with = "serde_hex_u64")]
+    pub energy: u64,
+    #[serde(default)]
+    pub energy_price: U256,
+    #[serde(with = "serde_hex_u64")]
+    pub nonce: u64,
+    /// Contract creation if not set.
+    #[serde(default)]
+    pub to: Option<B176>,
+    #[serde(default)]
+    pub value: U256,
+    #[serde(default, with = "serde_hex_bytes")]
+    pub input: Bytes,
+    /// Ed448 secret key of the sender, 57 bytes.
+    #[serde(default, deserialize_with = "deserialize_opt_str_as_bytes")]
+    pub secret_key: Option<Bytes>,
+}
+
+/// All inputs in one object, used when inputs are read from stdin.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct T8nInput {
+    #[serde(default)]
+    pub alloc: Option<Alloc>,
+    #[serde(default)]
+    pub env: Option<T8nEnv>,
+    #[serde(default)]
+    pub txs: Option<Vec<T8nTransaction>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct T8nResult {
+    pub state_root: B256,
+    pub receipts_root: B256,
+    pub logs_hash: B256,
+    #[serde(with = "serde_hex_bytes")]
+    pub logs_bloom: Bytes,
+    pub receipts: Vec<Receipt>,
+    pub rejected: Vec<RejectedTx>,
+    pub current_difficulty: U256,
+    #[serde(with = "serde_hex_u64")]
+    pub energy_used: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    #[serde(with = "serde_hex_u64")]
+    pub status: u64,
+    #[serde(with = "serde_hex_u64")]
+    pub cumulative_energy_used: u64,
+    #[serde(with = "serde_hex_bytes")]
+    pub logs_bloom: Bytes,
+    pub logs: Vec<Log>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<B176>,
+    #[serde(with = "serde_hex_u64")]
+    pub energy_used: u64,
+    #[serde(with = "serde_hex_u64")]
+    pub transaction_index: u64,
+}
+
+/// Transaction that could not be included, with its index in the input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RejectedTx {
+    pub index: usize,
+    pub error: String,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// Numbers as `0x` prefixed hex strings, decimal strings and JSON numbers are accepted as input.
+pub mod serde_hex_u64 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&format!("0x{value:x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Number {
+            Int(u64),
+            Str(String),
+        }
+
+        match Number::deserialize(d)? {
+            Number::Int(value) => Ok(value),
+            Number::Str(value) => match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => value.parse(),
+            }
+            .map_err(D::Error::custom),
+        }
+    }
+}
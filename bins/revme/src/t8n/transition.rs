@@ -0,0 +1,248 @@
+use super::models::{Alloc, AllocAccount, Receipt, RejectedTx, T8nEnv, T8nResult, T8nTransaction};
+use crate::{
+    reward::{apply_rewards, MAX_UNCLE_DEPTH},
+    statetest::{
+        caller_from_secret_key,
+        merkle_trie::{is_in_state_trie, log_rlp_hash, state_trie_root, Sha3Hasher},
+    },
+};
+use bytes::Bytes;
+use revm::{
+    interpreter::CreateScheme,
+    primitives::{
+        sha3, AccountInfo, Bytecode, Env, ExecutionResult, Log, Network, Output, SpecId,
+        TransactTo, B256, U256,
+    },
+    InMemoryDB,
+};
+use rlp::RlpStream;
+use thiserror::Error;
+use triehash::ordered_trie_root;
+
+/// Size of the logs bloom in bytes.
+pub const BLOOM_SIZE: usize = 256;
+
+#[derive(Debug, Error)]
+pub enum T8nError {
+    #[error("Unknown fork: {0}")]
+    UnknownFork(String),
+    #[error("Invalid block number of block hash: {0}")]
+    InvalidBlockHashNumber(String),
+    #[error("Ommer {index} is not one of the {MAX_UNCLE_DEPTH} previous blocks, delta: {delta}")]
+    InvalidOmmer { index: usize, delta: u64 },
+    #[error("Missing input: {0}")]
+    MissingInput(&'static str),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serde json error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Rules of the state transition that are not part of the inputs.
+#[derive(Clone, Copy, Debug)]
+pub struct TransitionConfig {
+    pub spec_id: SpecId,
+    pub network_id: u64,
+    /// Block reward, miner and ommers are not rewarded if not set.
+    pub reward: Option<U256>,
+}
+
+/// Apply transactions on top of `alloc` and return the result with the post state.
+pub fn transition(
+    alloc: &Alloc,
+    env: &T8nEnv,
+    txs: &[T8nTransaction],
+    config: TransitionConfig,
+) -> Result<(T8nResult, Alloc), T8nError> {
+    let mut database = InMemoryDB::default();
+    for (address, account) in alloc {
+        let code = Bytecode::new_raw(account.code.clone());
+        database.insert_account_info(
+            *address,
+            AccountInfo::new(account.balance, account.nonce, code),
+        );
+        for (&slot, &value) in &account.storage {
+            let _ = database.insert_account_storage(*address, slot, value);
+        }
+    }
+    for (number, hash) in &env.block_hashes {
+        let number: U256 = number
+            .parse()
+            .map_err(|_| T8nError::InvalidBlockHashNumber(number.clone()))?;
+        database.block_hashes.insert(number, *hash);
+    }
+
+    let mut block_env = Env::default();
+    block_env.cfg.network_id = config.network_id;
+    block_env.cfg.spec_id = config.spec_id;
+    block_env.block.number = env.current_number;
+    block_env.block.coinbase = env.current_coinbase;
+    block_env.block.timestamp = env.current_timestamp;
+    block_env.block.energy_limit = env.current_energy_limit;
+    block_env.block.difficulty = env.current_difficulty;
+
+    let network = Network::from(config.network_id);
+    let mut receipts = Vec::new();
+    let mut rejected = Vec::new();
+    let mut all_logs = Vec::new();
+    let mut energy_used = 0u64;
+    for (index, tx) in txs.iter().enumerate() {
+        let secret_key = tx.secret_key.as_deref().unwrap_or_default();
+        let Some(caller) = caller_from_secret_key(secret_key, network) else {
+            rejected.push(RejectedTx {
+                index,
+                error: "invalid Ed448 secret key".to_string(),
+            });
+            continue;
+        };
+        if U256::from(energy_used.saturating_add(tx.energy)) > env.current_energy_limit {
+            rejected.push(RejectedTx {
+                index,
+                error: "block energy limit reached".to_string(),
+            });
+            continue;
+        }
+
+        let mut env = block_env.clone();
+        env.tx.caller = caller;
+        env.tx.energy_limit = tx.energy;
+        env.tx.energy_price = tx.energy_price;
+        env.tx.nonce = Some(tx.nonce);
+        env.tx.data = tx.input.clone();
+        env.tx.value = tx.value;
+        env.tx.transact_to = match tx.to {
+            Some(address) => TransactTo::Call(address),
+            None => TransactTo::Create(CreateScheme::Create),
+        };
+
+        let mut evm = revm::new();
+        evm.database(&mut database);
+        evm.env = env;
+        let result = match evm.transact_commit() {
+            Ok(result) => result,
+            Err(error) => {
+                rejected.push(RejectedTx {
+                    index,
+                    error: error.to_string(),
+                });
+                continue;
+            }
+        };
+
+        energy_used += result.energy_used();
+        let contract_address = match &result {
+            ExecutionResult::Success {
+                output: Output::Create(_, address),
+                ..
+            } => *address,
+            _ => None,
+        };
+        let logs = result.logs();
+        receipts.push(Receipt {
+            status: result.is_success() as u64,
+            cumulative_energy_used: energy_used,
+            logs_bloom: logs_bloom(&logs),
+            logs: logs.clone(),
+            contract_address,
+            energy_used: result.energy_used(),
+            transaction_index: index as u64,
+        });
+        all_logs.extend(logs);
+    }
+
+    if let Some(reward) = config.reward {
+        let ommers: Vec<_> = env
+            .ommers
+            .iter()
+            .map(|ommer| (ommer.address, U256::from(ommer.delta)))
+            .collect();
+        apply_rewards(&mut database, reward, env.current_coinbase, &ommers).map_err(|index| {
+            T8nError::InvalidOmmer {
+                index,
+                delta: env.ommers[index].delta,
+            }
+        })?;
+    }
+
+    let accounts: Vec<_> = database
+        .accounts
+        .iter()
+        .filter(|(_address, acc)| is_in_state_trie(config.spec_id, acc))
+        .map(|(address, acc)| (*address, acc.clone()))
+        .collect();
+
+    let post_alloc = accounts
+        .iter()
+        .map(|(address, acc)| {
+            let code = database
+                .contracts
+                .get(&acc.info.code_hash)
+                .map(Bytecode::original_bytes)
+                .unwrap_or_default();
+            let account = AllocAccount {
+                balance: acc.info.balance,
+                code,
+                nonce: acc.info.nonce,
+                storage: acc
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| **value != U256::ZERO)
+                    .map(|(slot, value)| (*slot, *value))
+                    .collect(),
+            };
+            (*address, account)
+        })
+        .collect();
+
+    let result = T8nResult {
+        state_root: state_trie_root::<Sha3Hasher>(accounts.into_iter()),
+        receipts_root: B256(ordered_trie_root::<Sha3Hasher, _>(receipts.iter().map(receipt_rlp)).0),
+        logs_hash: log_rlp_hash(all_logs.clone()),
+        logs_bloom: logs_bloom(&all_logs),
+        receipts,
+        rejected,
+        current_difficulty: env.current_difficulty,
+        energy_used,
+    };
+    Ok((result, post_alloc))
+}
+
+/// 2048 bit bloom filter of log addresses and topics.
+pub fn logs_bloom(logs: &[Log]) -> Bytes {
+    let mut bloom = [0u8; BLOOM_SIZE];
+    for log in logs {
+        bloom_accrue(&mut bloom, log.address.as_bytes());
+        for topic in &log.topics {
+            bloom_accrue(&mut bloom, topic.as_bytes());
+        }
+    }
+    Bytes::from(bloom.to_vec())
+}
+
+/// Set three bits selected by the first six bytes of the `input` hash.
+fn bloom_accrue(bloom: &mut [u8; BLOOM_SIZE], input: &[u8]) {
+    let hash = sha3(input);
+    for i in [0, 2, 4] {
+        let bit = (usize::from(hash[i]) << 8 | usize::from(hash[i + 1])) & 2047;
+        bloom[BLOOM_SIZE - 1 - bit / 8] |= 1 << (bit % 8);
+    }
+}
+
+/// RLP of the receipt as stored in the receipts trie.
+fn receipt_rlp(receipt: &Receipt) -> Bytes {
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&receipt.status);
+    stream.append(&receipt.cumulative_energy_used);
+    stream.append(&receipt.logs_bloom.as_ref());
+    stream.begin_list(receipt.logs.len());
+    for log in &receipt.logs {
+        stream.begin_list(3);
+        stream.append(&log.address.0.as_ref());
+        stream.begin_list(log.topics.len());
+        for topic in &log.topics {
+            stream.append(&topic.0.as_ref());
+        }
+        stream.append(&log.data.as_ref());
+    }
+    stream.out().freeze()
+}
@@ -0,0 +1,37 @@
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+use super::diff::{first_divergence, parse_trace, Divergence, TraceDiffError};
+use structopt::StructOpt;
+
+/// Compare two EIP-3155 JSONL traces and report the first step where they diverge.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// Trace produced by `TracerEip3155` (`revme statetest --json`).
+    #[structopt(required = true)]
+    ours: PathBuf,
+    /// Reference trace, for example produced by go-core.
+    #[structopt(required = true)]
+    reference: PathBuf,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), TraceDiffError> {
+        let ours = parse_trace(BufReader::new(File::open(&self.ours)?))?;
+        let reference = parse_trace(BufReader::new(File::open(&self.reference)?))?;
+
+        match first_divergence(&ours, &reference) {
+            None => {
+                println!("Traces match ({} steps)", ours.steps.len());
+                Ok(())
+            }
+            Some(divergence) => {
+                println!("{divergence}");
+                let step = match divergence {
+                    Divergence::Step { step, .. } => step,
+                    Divergence::Summary { .. } => ours.steps.len(),
+                };
+                Err(TraceDiffError::Diverged { step })
+            }
+        }
+    }
+}
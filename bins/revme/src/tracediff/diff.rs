@@ -0,0 +1,375 @@
+use std::{fmt, io::BufRead};
+
+use revm::primitives::U256;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TraceDiffError {
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: serde json error: {source}")]
+    SerdeDeserialize {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("Line {line}: missing or malformed field `{field}`")]
+    InvalidField { line: usize, field: &'static str },
+    #[error("Traces diverged at step {step}")]
+    Diverged { step: usize },
+}
+
+/// One EIP-3155 step line. Values are normalized so that traces written by
+/// different implementations (hex strings vs numbers, padded stack items) compare equal.
+#[derive(Clone, Debug)]
+pub struct TraceStep {
+    /// Line number in the trace file, starting from 1.
+    pub line: usize,
+    pub pc: u64,
+    pub op: u8,
+    pub op_name: Option<String>,
+    pub energy: u64,
+    pub energy_cost: Option<u64>,
+    pub depth: u64,
+    pub stack: Vec<U256>,
+    /// Lowercase hex without `0x`, only present if memory tracing was enabled.
+    pub memory: Option<String>,
+    /// Lowercase hex without `0x`, only present if return data tracing was enabled.
+    pub return_data: Option<String>,
+}
+
+/// Final `{"output","energyUsed","error"}` line of the trace.
+#[derive(Clone, Debug)]
+pub struct TraceSummary {
+    pub line: usize,
+    pub output: String,
+    pub energy_used: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+    pub summary: Option<TraceSummary>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Divergence {
+    /// Steps at index `step` differ, or one of the traces ended before the other.
+    Step {
+        step: usize,
+        /// Last step both traces agree on.
+        previous: Option<TraceStep>,
+        ours: Option<TraceStep>,
+        reference: Option<TraceStep>,
+        /// Differing fields, empty if one trace is shorter.
+        fields: Vec<&'static str>,
+    },
+    /// All steps match but the outcome of the transaction does not.
+    Summary {
+        ours: Option<TraceSummary>,
+        reference: Option<TraceSummary>,
+        fields: Vec<&'static str>,
+    },
+}
+
+/// Parse JSONL trace. Lines that are not json objects are skipped so that
+/// a trace captured from stdout together with other output can be used as is.
+/// Json objects that are neither a step nor a summary (e.g. `stateRoot`) are skipped too.
+pub fn parse_trace<R: BufRead>(reader: R) -> Result<Trace, TraceDiffError> {
+    let mut trace = Trace::default();
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+        let value: Value =
+            serde_json::from_str(line).map_err(|source| TraceDiffError::SerdeDeserialize {
+                line: line_number,
+                source,
+            })?;
+        if let Some(object) = value.as_object() {
+            if object.contains_key("pc") {
+                trace.steps.push(parse_step(line_number, object)?);
+            } else if object.contains_key("output") {
+                trace.summary = Some(parse_summary(line_number, object)?);
+            }
+        }
+    }
+    Ok(trace)
+}
+
+/// Return first point where two traces diverge, `None` if they are the same.
+pub fn first_divergence(ours: &Trace, reference: &Trace) -> Option<Divergence> {
+    let len = ours.steps.len().max(reference.steps.len());
+    for step in 0..len {
+        let (our_step, reference_step) = (ours.steps.get(step), reference.steps.get(step));
+        let fields = match (our_step, reference_step) {
+            (Some(our_step), Some(reference_step)) => our_step.diff(reference_step),
+            _ => Vec::new(),
+        };
+        if our_step.is_none() || reference_step.is_none() || !fields.is_empty() {
+            return Some(Divergence::Step {
+                step,
+                previous: step.checked_sub(1).map(|prev| ours.steps[prev].clone()),
+                ours: our_step.cloned(),
+                reference: reference_step.cloned(),
+                fields,
+            });
+        }
+    }
+
+    let fields = match (&ours.summary, &reference.summary) {
+        (Some(our_summary), Some(reference_summary)) => our_summary.diff(reference_summary),
+        (None, None) => Vec::new(),
+        _ => vec!["summary"],
+    };
+    if fields.is_empty() {
+        return None;
+    }
+    Some(Divergence::Summary {
+        ours: ours.summary.clone(),
+        reference: reference.summary.clone(),
+        fields,
+    })
+}
+
+impl TraceStep {
+    /// Names of the fields that differ. `energyCost` and `opName` are only informative
+    /// and not compared, memory and return data are compared only if both traces have them.
+    pub fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.pc != other.pc {
+            fields.push("pc");
+        }
+        if self.op != other.op {
+            fields.push("op");
+        }
+        if self.energy != other.energy {
+            fields.push("energy");
+        }
+        if self.depth != other.depth {
+            fields.push("depth");
+        }
+        if self.stack != other.stack {
+            fields.push("stack");
+        }
+        if matches!((&self.memory, &other.memory), (Some(a), Some(b)) if a != b) {
+            fields.push("memory");
+        }
+        if matches!((&self.return_data, &other.return_data), (Some(a), Some(b)) if a != b) {
+            fields.push("returnData");
+        }
+        fields
+    }
+}
+
+impl TraceSummary {
+    /// Error messages are compared as strings, tracer reports errors with the messages of go-core.
+    pub fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.output != other.output {
+            fields.push("output");
+        }
+        if self.energy_used != other.energy_used {
+            fields.push("energyUsed");
+        }
+        if self.error != other.error {
+            fields.push("error");
+        }
+        fields
+    }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: pc={} op={:#04x}({}) energy={:#x}",
+            self.line,
+            self.pc,
+            self.op,
+            self.op_name.as_deref().unwrap_or("?"),
+            self.energy,
+        )?;
+        if let Some(cost) = self.energy_cost {
+            write!(f, " cost={cost:#x}")?;
+        }
+        write!(f, " depth={} stack=[", self.depth)?;
+        for (i, item) in self.stack.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{item:#x}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl fmt::Display for TraceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: output=0x{} energyUsed={:#x} error={}",
+            self.line,
+            self.output,
+            self.energy_used,
+            self.error.as_deref().unwrap_or("none"),
+        )
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Step {
+                step,
+                previous,
+                ours,
+                reference,
+                fields,
+            } => {
+                writeln!(f, "Traces diverge at step {step}")?;
+                if !fields.is_empty() {
+                    writeln!(f, "  differing fields: {}", fields.join(", "))?;
+                }
+                if let Some(previous) = previous {
+                    writeln!(f, "  previous:  {previous}")?;
+                }
+                match ours {
+                    Some(ours) => writeln!(f, "  ours:      {ours}")?,
+                    None => writeln!(f, "  ours:      <trace ended>")?,
+                }
+                match reference {
+                    Some(reference) => writeln!(f, "  reference: {reference}")?,
+                    None => writeln!(f, "  reference: <trace ended>")?,
+                }
+                if let (Some(ours), Some(reference)) = (ours, reference) {
+                    if fields.contains(&"memory") {
+                        writeln!(f, "  ours memory:      0x{}", ours.memory.as_ref().unwrap())?;
+                        writeln!(
+                            f,
+                            "  reference memory: 0x{}",
+                            reference.memory.as_ref().unwrap()
+                        )?;
+                    }
+                    if fields.contains(&"returnData") {
+                        writeln!(
+                            f,
+                            "  ours returnData:      0x{}",
+                            ours.return_data.as_ref().unwrap()
+                        )?;
+                        writeln!(
+                            f,
+                            "  reference returnData: 0x{}",
+                            reference.return_data.as_ref().unwrap()
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Summary {
+                ours,
+                reference,
+                fields,
+            } => {
+                writeln!(f, "Traces have same steps but different outcome")?;
+                writeln!(f, "  differing fields: {}", fields.join(", "))?;
+                match ours {
+                    Some(ours) => writeln!(f, "  ours:      {ours}")?,
+                    None => writeln!(f, "  ours:      <no summary>")?,
+                }
+                match reference {
+                    Some(reference) => writeln!(f, "  reference: {reference}"),
+                    None => writeln!(f, "  reference: <no summary>"),
+                }
+            }
+        }
+    }
+}
+
+fn parse_step(line: usize, object: &Map<String, Value>) -> Result<TraceStep, TraceDiffError> {
+    let invalid = |field| TraceDiffError::InvalidField { line, field };
+    let number = |field| object.get(field).and_then(parse_u64);
+
+    let stack = object
+        .get("stack")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("stack"))?
+        .iter()
+        .map(|item| item.as_str().and_then(parse_u256))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| invalid("stack"))?;
+
+    Ok(TraceStep {
+        line,
+        pc: number("pc").ok_or_else(|| invalid("pc"))?,
+        op: number("op")
+            .and_then(|op| u8::try_from(op).ok())
+            .ok_or_else(|| invalid("op"))?,
+        op_name: object
+            .get("opName")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+        energy: number("energy").ok_or_else(|| invalid("energy"))?,
+        energy_cost: number("energyCost"),
+        depth: number("depth").ok_or_else(|| invalid("depth"))?,
+        stack,
+        memory: object
+            .get("memory")
+            .and_then(Value::as_str)
+            .map(normalize_hex),
+        return_data: object
+            .get("returnData")
+            .and_then(Value::as_str)
+            .map(normalize_hex),
+    })
+}
+
+fn parse_summary(line: usize, object: &Map<String, Value>) -> Result<TraceSummary, TraceDiffError> {
+    let invalid = |field| TraceDiffError::InvalidField { line, field };
+    Ok(TraceSummary {
+        line,
+        output: object
+            .get("output")
+            .and_then(Value::as_str)
+            .map(normalize_hex)
+            .ok_or_else(|| invalid("output"))?,
+        energy_used: object
+            .get("energyUsed")
+            .and_then(parse_u64)
+            .ok_or_else(|| invalid("energyUsed"))?,
+        error: object
+            .get("error")
+            .and_then(Value::as_str)
+            .filter(|error| !error.is_empty())
+            .map(str::to_owned),
+    })
+}
+
+/// Numbers are written either as json numbers, `0x` prefixed hex or decimal strings.
+fn parse_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => string.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+fn parse_u256(item: &str) -> Option<U256> {
+    let hex = item.strip_prefix("0x").unwrap_or(item);
+    if hex.is_empty() {
+        return Some(U256::ZERO);
+    }
+    U256::from_str_radix(hex, 16).ok()
+}
+
+fn normalize_hex(hex: &str) -> String {
+    hex.strip_prefix("0x").unwrap_or(hex).to_lowercase()
+}
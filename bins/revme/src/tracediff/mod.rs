@@ -0,0 +1,5 @@
+mod cmd;
+pub mod diff;
+
+pub use cmd::Cmd;
+pub use diff::TraceDiffError as Error;
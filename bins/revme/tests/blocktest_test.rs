@@ -0,0 +1,274 @@
+use revm::primitives::{B256, U256};
+use revme::blocktest::{
+    models::BlockchainTestSuite,
+    runner::{execute_test_unit, BlockTestError},
+};
+
+// contract stores hash of the previous block under current block number. Third block reuses
+// nonce of the first transaction and is rejected.
+const FIXTURE: &str = r#"{
+  "blockHashHistory": {
+    "network": "Istanbul",
+    "genesisBlockHeader": {
+      "bloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+      "coinbase": "0x00000000000000000000000000000000000000000200",
+      "difficulty": "0x20000",
+      "energyLimit": "0x989680",
+      "energyUsed": "0x0",
+      "extraData": "0x",
+      "hash": "0x9922bb11b1c82b52cabcb01bef6048aa88c5fd7b74c4d8e8844076e40fc9a39d",
+      "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "nonce": "0x0000000000000000",
+      "number": "0x0",
+      "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "receiptTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "timestamp": "0x0",
+      "transactionsTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+      "uncleHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+    },
+    "pre": {
+      "0x00000000000000000000000000000000000000000100": {
+        "balance": "0x0",
+        "code": "0x6001430340435500",
+        "nonce": "0x0",
+        "storage": {}
+      },
+      "0xcb11b451c7cd5a1610c8aa18fd9013d0e7dcc4501a31": {
+        "balance": "0xde0b6b3a7640000",
+        "code": "0x",
+        "nonce": "0x0",
+        "storage": {}
+      }
+    },
+    "blocks": [
+      {
+        "blockHeader": {
+          "bloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+          "coinbase": "0x00000000000000000000000000000000000000000200",
+          "difficulty": "0x20000",
+          "energyLimit": "0x989680",
+          "energyUsed": "0xa046",
+          "extraData": "0x",
+          "hash": "0xa5092bfe45643813b0b23c594e6a8eb6f2f2243d62c9b335a3fa56a2d4658cf1",
+          "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "nonce": "0x0000000000000000",
+          "number": "0x1",
+          "parentHash": "0x9922bb11b1c82b52cabcb01bef6048aa88c5fd7b74c4d8e8844076e40fc9a39d",
+          "receiptTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "stateRoot": "0xda1cda18b7adc92a365189fb3eee4735745215d192b07fdbaccaf48a0686f045",
+          "timestamp": "0xa",
+          "transactionsTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "uncleHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        },
+        "transactions": [
+          {
+            "data": "0x",
+            "energyLimit": "0x186a0",
+            "energyPrice": "0xa",
+            "nonce": "0x0",
+            "secretKey": "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101",
+            "to": "0x00000000000000000000000000000000000000000100",
+            "value": "0x0"
+          }
+        ]
+      },
+      {
+        "blockHeader": {
+          "bloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+          "coinbase": "0x00000000000000000000000000000000000000000200",
+          "difficulty": "0x20000",
+          "energyLimit": "0x989680",
+          "energyUsed": "0xf24e",
+          "extraData": "0x",
+          "hash": "0xc7b6b929e106efd8c3f43669815657f2b1d3a038a8938aed734e7fd77bfa3281",
+          "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "nonce": "0x0000000000000000",
+          "number": "0x2",
+          "parentHash": "0xa5092bfe45643813b0b23c594e6a8eb6f2f2243d62c9b335a3fa56a2d4658cf1",
+          "receiptTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "stateRoot": "0x61d868e3e4b60d980b682a3f2acf04fa713bfa93951551bfec8e62600d32e708",
+          "timestamp": "0x14",
+          "transactionsTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "uncleHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        },
+        "transactions": [
+          {
+            "data": "0x",
+            "energyLimit": "0x186a0",
+            "energyPrice": "0xa",
+            "nonce": "0x1",
+            "secretKey": "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101",
+            "to": "0x00000000000000000000000000000000000000000100",
+            "value": "0x0"
+          },
+          {
+            "data": "0x",
+            "energyLimit": "0x5208",
+            "energyPrice": "0xa",
+            "nonce": "0x2",
+            "secretKey": "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101",
+            "to": "0x00000000000000000000000000000000000000000300",
+            "value": "0x3e8"
+          }
+        ]
+      },
+      {
+        "blockHeader": {
+          "bloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+          "coinbase": "0x00000000000000000000000000000000000000000200",
+          "difficulty": "0x20000",
+          "energyLimit": "0x989680",
+          "energyUsed": "0x5208",
+          "extraData": "0x",
+          "hash": "0xc1371b4d5b423ffae349441a8dd1f1c089b2156e85c43166ab0ec0996930df27",
+          "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "nonce": "0x0000000000000000",
+          "number": "0x3",
+          "parentHash": "0xc7b6b929e106efd8c3f43669815657f2b1d3a038a8938aed734e7fd77bfa3281",
+          "receiptTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "timestamp": "0x1e",
+          "transactionsTrie": "0x0000000000000000000000000000000000000000000000000000000000000000",
+          "uncleHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        },
+        "transactions": [
+          {
+            "data": "0x",
+            "energyLimit": "0x5208",
+            "energyPrice": "0xa",
+            "nonce": "0x0",
+            "secretKey": "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101",
+            "to": "0x00000000000000000000000000000000000000000300",
+            "value": "0x3e8"
+          }
+        ],
+        "expectException": "TR_NonceTooLow"
+      }
+    ],
+    "postState": {
+      "0x00000000000000000000000000000000000000000100": {
+        "balance": "0x0",
+        "code": "0x6001430340435500",
+        "nonce": "0x0",
+        "storage": {
+          "0x01": "0x9922bb11b1c82b52cabcb01bef6048aa88c5fd7b74c4d8e8844076e40fc9a39d",
+          "0x02": "0xa5092bfe45643813b0b23c594e6a8eb6f2f2243d62c9b335a3fa56a2d4658cf1"
+        }
+      },
+      "0x00000000000000000000000000000000000000000200": {
+        "balance": "0x3782dace9d9fb9c8",
+        "code": "0x",
+        "nonce": "0x0",
+        "storage": {}
+      },
+      "0x00000000000000000000000000000000000000000300": {
+        "balance": "0x3e8",
+        "code": "0x",
+        "nonce": "0x0",
+        "storage": {}
+      },
+      "0xcb11b451c7cd5a1610c8aa18fd9013d0e7dcc4501a31": {
+        "balance": "0xde0b6b3a7544250",
+        "code": "0x",
+        "nonce": "0x3",
+        "storage": {}
+      }
+    },
+    "lastblockhash": "0xc7b6b929e106efd8c3f43669815657f2b1d3a038a8938aed734e7fd77bfa3281"
+  }
+}"#;
+
+fn fixture() -> BlockchainTestSuite {
+    serde_json::from_str(FIXTURE).unwrap()
+}
+
+#[test]
+fn test_blocks_import() {
+    let suite = fixture();
+    execute_test_unit(&suite.0["blockHashHistory"]).unwrap();
+}
+
+#[test]
+fn test_invalid_block_not_expected() {
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    unit.blocks[2].expect_exception = None;
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::InvalidTransaction { index: 0, .. })
+    ));
+}
+
+#[test]
+fn test_last_block_hash_and_post_state() {
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    unit.lastblockhash = B256::zero();
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::LastBlockHashMismatch { .. })
+    ));
+
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    let contract = unit.post_state.keys().min().copied().unwrap();
+    unit.post_state
+        .get_mut(&contract)
+        .unwrap()
+        .storage
+        .insert(U256::from(3), U256::from(1));
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::PostStateMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_block_does_not_extend_parent() {
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    unit.blocks[1].block_header.as_mut().unwrap().parent_hash = B256::zero();
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::ParentHashMismatch { .. })
+    ));
+
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    unit.blocks[1].block_header.as_mut().unwrap().number = U256::from(3);
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::BlockNumberMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_header_hash() {
+    let mut suite = fixture();
+    let unit = suite.0.get_mut("blockHashHistory").unwrap();
+    unit.blocks[0].block_header.as_mut().unwrap().timestamp = U256::from(11);
+    assert!(matches!(
+        execute_test_unit(unit),
+        Err(BlockTestError::HeaderHashMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_invalid_uncle() {
+    for number in [U256::from(1), U256::MAX] {
+        let mut suite = fixture();
+        let unit = suite.0.get_mut("blockHashHistory").unwrap();
+        let mut uncle = fixture()
+            .0
+            .remove("blockHashHistory")
+            .unwrap()
+            .genesis_block_header;
+        uncle.number = number;
+        unit.blocks[0].uncle_headers.push(uncle);
+        assert!(matches!(
+            execute_test_unit(unit),
+            Err(BlockTestError::InvalidUncle { .. })
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+use revm::primitives::{Network, B256, U256};
+use revme::filltest::{
+    filler::{check, fill, FillError},
+    models::{CoreAccount, CoreTestSuite},
+};
+use revme::statetest::caller_from_secret_key;
+
+// stores calldata size in slot 0 and logs it.
+const TEMPLATE: &str = r#"{
+  "storeCalldataSize": {
+    "env": {
+      "coinbase": "0x00000000000000000000000000000000000000000200",
+      "number": "0x1",
+      "timestamp": "0x3e8",
+      "energyLimit": "0x989680"
+    },
+    "pre": {
+      "0x00000000000000000000000000000000000000000100": {
+        "balance": "0x0",
+        "code": "0x3660005560206000a0"
+      }
+    },
+    "transaction": {
+      "data": ["0x", "0x0102"],
+      "energyLimit": [100000],
+      "secretKey": "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101",
+      "to": "0x00000000000000000000000000000000000000000100",
+      "value": ["0x0", "0x1"]
+    },
+    "forks": ["PETERSBURG", "ISTANBUL"]
+  }
+}"#;
+
+#[test]
+fn test_fill_and_check() {
+    let mut suite: CoreTestSuite = serde_json::from_str(TEMPLATE).unwrap();
+    let unit = suite.0.get_mut("storeCalldataSize").unwrap();
+    let caller = caller_from_secret_key(&unit.transaction.secret_key, Network::Mainnet).unwrap();
+    unit.pre.insert(
+        caller,
+        CoreAccount {
+            balance: U256::from(10).pow(U256::from(18)),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(check(unit), Err(FillError::NotFilled { .. })));
+
+    fill(unit).unwrap();
+    assert_eq!(unit.post.len(), 2);
+    assert!(unit.post.values().all(|states| states.len() == 4));
+    check(unit).unwrap();
+
+    // filled test survives a json round trip.
+    let filled: CoreTestSuite =
+        serde_json::from_str(&serde_json::to_string_pretty(&suite).unwrap()).unwrap();
+    assert_eq!(filled, suite);
+
+    let unit = suite.0.get_mut("storeCalldataSize").unwrap();
+    let states = unit.post.values_mut().next().unwrap();
+    states[1].hash = B256::zero();
+    assert!(matches!(check(unit), Err(FillError::Mismatch { .. })));
+}
@@ -0,0 +1,129 @@
+use revm::primitives::{create_address, Network, B176, U256};
+use revme::t8n::{
+    models::{Alloc, T8nEnv, T8nTransaction},
+    transition::{transition, T8nError, TransitionConfig, BLOOM_SIZE},
+};
+
+const SECRET_KEY: &str = "0x010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101";
+const SENDER: &str = "0xcb11b451c7cd5a1610c8aa18fd9013d0e7dcc4501a31";
+
+fn inputs(txs: &str) -> (Alloc, T8nEnv, Vec<T8nTransaction>) {
+    // contract emits a log with topic 1.
+    let alloc = format!(
+        r#"{{
+        "0x00000000000000000000000000000000000000000100": {{ "code": "0x600160006000a100" }},
+        "{SENDER}": {{ "balance": "0xde0b6b3a7640000" }}
+    }}"#
+    );
+    let env = r#"{
+        "currentCoinbase": "0x00000000000000000000000000000000000000000200",
+        "currentDifficulty": "0x20000",
+        "currentEnergyLimit": "0x989680",
+        "currentNumber": "0x1",
+        "currentTimestamp": "0x3e8",
+        "blockHashes": { "0": "0x1000000000000000000000000000000000000000000000000000000000000000" }
+    }"#;
+    (
+        serde_json::from_str(&alloc).unwrap(),
+        serde_json::from_str(env).unwrap(),
+        serde_json::from_str(&txs.replace("SECRET_KEY", SECRET_KEY)).unwrap(),
+    )
+}
+
+#[test]
+fn test_transition() {
+    let (alloc, env, txs) = inputs(
+        r#"[
+        { "energy": 100000, "nonce": "0x0", "to": "0x00000000000000000000000000000000000000000100", "secretKey": "SECRET_KEY" },
+        { "energy": "0x5208", "nonce": "0x0", "to": "0x00000000000000000000000000000000000000000300", "secretKey": "SECRET_KEY" },
+        { "energy": "0x5208", "nonce": "0x1", "to": "0x00000000000000000000000000000000000000000300" },
+        { "energy": "0x186a0", "nonce": "0x1", "to": null, "input": "0x", "secretKey": "SECRET_KEY" }
+    ]"#,
+    );
+    let reward = U256::from(2_000_000_000_000_000_000u64);
+    let config = TransitionConfig {
+        spec_id: revm::primitives::SpecId::ISTANBUL,
+        network_id: 1,
+        reward: Some(reward),
+    };
+    let (result, post) = transition(&alloc, &env, &txs, config).unwrap();
+
+    // second tx reuses the nonce and third is not signed.
+    let rejected: Vec<_> = result.rejected.iter().map(|tx| tx.index).collect();
+    assert_eq!(rejected, [1, 2]);
+    assert_eq!(result.receipts.len(), 2);
+    let (call, create) = (&result.receipts[0], &result.receipts[1]);
+    assert_eq!(call.logs.len(), 1);
+    assert_eq!(call.logs_bloom, result.logs_bloom);
+    assert!(result.logs_bloom.iter().any(|byte| *byte != 0));
+    assert!(create.logs_bloom.iter().all(|byte| *byte == 0));
+    assert_eq!(create.transaction_index, 3);
+    let sender: B176 = SENDER.parse().unwrap();
+    assert_eq!(
+        create.contract_address,
+        Some(create_address(sender, 1, Network::Mainnet))
+    );
+    assert_eq!(
+        create.cumulative_energy_used,
+        call.energy_used + create.energy_used
+    );
+    assert_eq!(result.energy_used, create.cumulative_energy_used);
+
+    assert_eq!(post[&sender].nonce, 2);
+    let coinbase: B176 = "0x00000000000000000000000000000000000000000200"
+        .parse()
+        .unwrap();
+    assert_eq!(post[&coinbase].balance, reward);
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        json["energyUsed"],
+        format!("0x{:x}", result.energy_used).as_str()
+    );
+    assert_eq!(json["rejected"][0]["index"], 1);
+}
+
+#[test]
+fn test_empty_block() {
+    let (alloc, env, txs) = inputs("[]");
+    let config = TransitionConfig {
+        spec_id: revm::primitives::SpecId::ISTANBUL,
+        network_id: 1,
+        reward: None,
+    };
+    let (result, post) = transition(&alloc, &env, &txs, config).unwrap();
+    assert_eq!(result.energy_used, 0);
+    assert_eq!(result.logs_bloom.len(), BLOOM_SIZE);
+    assert!(result.receipts.is_empty());
+    // without reward post state is the pre state.
+    assert_eq!(post, alloc);
+
+    let (other, _) = transition(&post, &env, &txs, config).unwrap();
+    assert_eq!(other.state_root, result.state_root);
+    assert_eq!(other.receipts_root, result.receipts_root);
+}
+
+#[test]
+fn test_ommer_rewards() {
+    let (alloc, mut env, txs) = inputs("[]");
+    let config = TransitionConfig {
+        spec_id: revm::primitives::SpecId::ISTANBUL,
+        network_id: 1,
+        reward: Some(U256::from(3_200)),
+    };
+    let ommer: B176 = "0x00000000000000000000000000000000000000000400"
+        .parse()
+        .unwrap();
+    env.ommers = serde_json::from_str(&format!(
+        r#"[{{ "delta": "0x2", "address": "{ommer:?}" }}]"#
+    ))
+    .unwrap();
+    let (_, post) = transition(&alloc, &env, &txs, config).unwrap();
+    assert_eq!(post[&ommer].balance, U256::from(2_400));
+
+    env.ommers[0].delta = 7;
+    assert!(matches!(
+        transition(&alloc, &env, &txs, config),
+        Err(T8nError::InvalidOmmer { index: 0, delta: 7 })
+    ));
+}
@@ -0,0 +1,89 @@
+use revme::tracediff::diff::{first_divergence, parse_trace, Divergence};
+
+const OURS: &str = r#"Start running tests on: "add.json"
+{"pc":0,"op":96,"energy":"0x5f5e100","energyCost":"0x3","memSize":0,"stack":[],"depth":1,"refund":0,"opName":"PUSH1"}
+{"pc":2,"op":96,"energy":"0x5f5e0fd","energyCost":"0x3","memSize":0,"stack":["0x1"],"depth":1,"refund":0,"opName":"PUSH1"}
+{"pc":4,"op":1,"energy":"0x5f5e0fa","energyCost":"0x3","memSize":0,"stack":["0x1","0x2"],"depth":1,"refund":0,"opName":"ADD"}
+{"output":"","energyUsed":"0x9"}
+"#;
+
+#[test]
+fn test_tracediff_same_trace() {
+    // reference trace uses numbers and zero padded stack items.
+    let reference = r#"{"pc":0,"op":96,"energy":100000000,"energyCost":3,"memSize":0,"stack":[],"depth":1,"refund":0,"opName":"PUSH1"}
+{"pc":2,"op":96,"energy":99999997,"energyCost":3,"memSize":0,"stack":["0x0000000000000000000000000000000000000000000000000000000000000001"],"depth":1,"refund":0,"opName":"PUSH1"}
+{"pc":4,"op":1,"energy":99999994,"energyCost":3,"memSize":0,"stack":["0x01","0x02"],"depth":1,"refund":0,"opName":"ADD"}
+{"stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000"}
+{"output":"","energyUsed":"0x9","error":""}
+"#;
+    let ours = parse_trace(OURS.as_bytes()).unwrap();
+    let reference = parse_trace(reference.as_bytes()).unwrap();
+    assert_eq!(ours.steps.len(), 3);
+    assert!(first_divergence(&ours, &reference).is_none());
+}
+
+#[test]
+fn test_tracediff_first_diverging_step() {
+    let reference = r#"{"pc":0,"op":96,"energy":"0x5f5e100","stack":[],"depth":1}
+{"pc":2,"op":96,"energy":"0x5f5e0fd","stack":["0x1"],"depth":1}
+{"pc":4,"op":1,"energy":"0x5f5e0f9","stack":["0x1","0x3"],"depth":1}
+{"output":"","energyUsed":"0xa"}
+"#;
+    let ours = parse_trace(OURS.as_bytes()).unwrap();
+    let reference = parse_trace(reference.as_bytes()).unwrap();
+    match first_divergence(&ours, &reference) {
+        Some(Divergence::Step {
+            step,
+            previous,
+            ours,
+            reference,
+            fields,
+        }) => {
+            assert_eq!(step, 2);
+            assert_eq!(previous.unwrap().pc, 2);
+            assert_eq!(ours.unwrap().line, 4);
+            assert_eq!(reference.unwrap().line, 3);
+            assert_eq!(fields, vec!["energy", "stack"]);
+        }
+        divergence => panic!("unexpected divergence: {divergence:?}"),
+    }
+}
+
+#[test]
+fn test_tracediff_shorter_trace() {
+    let reference = r#"{"pc":0,"op":96,"energy":"0x5f5e100","stack":[],"depth":1}
+{"pc":2,"op":96,"energy":"0x5f5e0fd","stack":["0x1"],"depth":1}
+"#;
+    let ours = parse_trace(OURS.as_bytes()).unwrap();
+    let reference = parse_trace(reference.as_bytes()).unwrap();
+    match first_divergence(&ours, &reference) {
+        Some(Divergence::Step {
+            step,
+            reference,
+            fields,
+            ..
+        }) => {
+            assert_eq!(step, 2);
+            assert!(reference.is_none());
+            assert!(fields.is_empty());
+        }
+        divergence => panic!("unexpected divergence: {divergence:?}"),
+    }
+}
+
+#[test]
+fn test_tracediff_summary_error() {
+    let steps = OURS.lines().skip(1).take(3).collect::<Vec<_>>().join("\n");
+    let trace = |error: &str| {
+        let summary = format!(r#"{{"output":"","energyUsed":"0x9","error":"{error}"}}"#);
+        parse_trace(format!("{steps}\n{summary}\n").as_bytes()).unwrap()
+    };
+    let ours = trace("out of energy");
+    assert!(first_divergence(&ours, &trace("out of energy")).is_none());
+    for reference in ["", "stack underflow (0 <=> 1)"] {
+        match first_divergence(&ours, &trace(reference)) {
+            Some(Divergence::Summary { fields, .. }) => assert_eq!(fields, vec!["error"]),
+            divergence => panic!("unexpected divergence: {divergence:?}"),
+        }
+    }
+}
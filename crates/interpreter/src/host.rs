@@ -3,7 +3,7 @@ mod dummy_host;
 use crate::primitives::Bytecode;
 use crate::{
     primitives::{Bytes, Env, B176, B256, U256},
-    CallInputs, CreateInputs, Energy, InstructionResult, Interpreter, SelfDestructResult,
+    InstructionResult, Interpreter, SelfDestructResult,
 };
 pub use dummy_host::DummyHost;
 
@@ -43,11 +43,4 @@ pub trait Host {
     fn log(&mut self, address: B176, topics: Vec<B256>, data: Bytes);
     /// Mark an address to be deleted, with funds transferred to target.
     fn selfdestruct(&mut self, address: B176, target: B176) -> Option<SelfDestructResult>;
-    /// Invoke a create operation.
-    fn create(
-        &mut self,
-        inputs: &mut CreateInputs,
-    ) -> (InstructionResult, Option<B176>, Energy, Bytes);
-    /// Invoke a call operation.
-    fn call(&mut self, input: &mut CallInputs) -> (InstructionResult, Energy, Bytes);
 }
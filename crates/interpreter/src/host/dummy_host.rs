@@ -1,7 +1,7 @@
 use crate::primitives::{hash_map::Entry, Bytecode, Bytes, HashMap, U256};
 use crate::{
     primitives::{Env, Log, B176, B256, SHA3_EMPTY},
-    CallInputs, CreateInputs, Energy, Host, InstructionResult, Interpreter, SelfDestructResult,
+    Host, InstructionResult, Interpreter, SelfDestructResult,
 };
 
 pub struct DummyHost {
@@ -100,15 +100,4 @@ impl Host for DummyHost {
     fn selfdestruct(&mut self, _address: B176, _target: B176) -> Option<SelfDestructResult> {
         panic!("Create is not supported for this host")
     }
-
-    fn create(
-        &mut self,
-        _inputs: &mut CreateInputs,
-    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
-        panic!("Create is not supported for this host")
-    }
-
-    fn call(&mut self, _input: &mut CallInputs) -> (InstructionResult, Energy, Bytes) {
-        panic!("Call is not supported for this host")
-    }
 }
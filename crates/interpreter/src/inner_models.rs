@@ -1,7 +1,10 @@
 pub use crate::primitives::CreateScheme;
 use crate::primitives::{Bytes, B176, U256};
+use alloc::boxed::Box;
+use core::ops::Range;
 
 /// Inputs for a call.
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallInputs {
     /// The target of the call.
@@ -22,6 +25,7 @@ pub struct CallInputs {
     pub is_static: bool,
 }
 
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreateInputs {
     pub caller: B176,
@@ -35,6 +39,20 @@ pub struct CreateInputs {
     pub energy_limit: u64,
 }
 
+/// Call or create requested by the interpreter. Interpreter is suspended with
+/// [crate::InstructionResult::CallOrCreate] until host runs the new frame and inserts its outcome.
+#[derive(Clone, Debug)]
+pub enum InterpreterAction {
+    Call {
+        inputs: Box<CallInputs>,
+        /// Memory range where the caller expects output of the call.
+        return_memory_offset: Range<usize>,
+    },
+    Create {
+        inputs: Box<CreateInputs>,
+    },
+}
+
 /// Call schemes.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
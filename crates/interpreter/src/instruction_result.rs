@@ -10,6 +10,14 @@ pub enum InstructionResult {
     Return = 0x02,
     SelfDestruct = 0x03,
 
+    // action codes
+    /// Interpreter is suspended until host executes [crate::InterpreterAction]
+    /// stored in [crate::Interpreter::next_action]. Never returned as final result.
+    CallOrCreate = 0x10,
+    /// Returned by host before executing an instruction whose data is not available,
+    /// execution can be resumed from the same instruction. Never returned as final result.
+    Suspend,
+
     // revert code
     Revert = 0x20, // revert opcode
     CallTooDeep = 0x21,
@@ -41,6 +49,14 @@ pub enum InstructionResult {
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitcodeSizeLimit,
 
+    // execution limits, returned by inspector.
+    /// Instruction count limit exceeded.
+    StepLimitExceeded,
+    /// Wall-clock time limit exceeded.
+    TimeLimitExceeded,
+    /// Limit on total memory across all call frames exceeded.
+    MemoryBudgetExceeded,
+
     // Fatal external error. Returned by database.
     FatalExternalError,
 }
@@ -51,8 +67,14 @@ pub enum SuccessOrHalt {
     Revert,
     Halt(Halt),
     FatalExternalError,
+    /// Precompile failed, the error is kept by the host as it is not part of [InstructionResult].
+    PrecompileError,
     // this is internal opcode.
     InternalContinue,
+    // internal, interpreter is waiting for call or create to finish.
+    InternalCallOrCreate,
+    // internal, execution is suspended until data is available.
+    InternalSuspend,
 }
 
 impl SuccessOrHalt {
@@ -76,10 +98,14 @@ impl SuccessOrHalt {
 
     /// Returns true if the EVM has experienced an exceptional halt
     pub fn is_halt(&self) -> bool {
-        matches!(self, SuccessOrHalt::Halt(_))
+        matches!(
+            self,
+            SuccessOrHalt::Halt(_) | SuccessOrHalt::PrecompileError
+        )
     }
 
-    /// Returns the [Halt] value the EVM has experienced an exceptional halt
+    /// Returns the [Halt] value the EVM has experienced an exceptional halt,
+    /// `None` for [SuccessOrHalt::PrecompileError] as its error is kept by the host.
     pub fn to_halt(self) -> Option<Halt> {
         match self {
             SuccessOrHalt::Halt(halt) => Some(halt),
@@ -92,6 +118,8 @@ impl From<InstructionResult> for SuccessOrHalt {
     fn from(result: InstructionResult) -> Self {
         match result {
             InstructionResult::Continue => Self::InternalContinue, // used only in interpreter loop
+            InstructionResult::CallOrCreate => Self::InternalCallOrCreate, // used only by frame loop
+            InstructionResult::Suspend => Self::InternalSuspend, // used only by frame loop
             InstructionResult::Stop => Self::Success(Eval::Stop),
             InstructionResult::Return => Self::Success(Eval::Return),
             InstructionResult::SelfDestruct => Self::Success(Eval::SelfDestruct),
@@ -128,13 +156,22 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::OutOfOffset => Self::Halt(Halt::OutOfOffset),
             InstructionResult::CreateCollision => Self::Halt(Halt::CreateCollision),
             InstructionResult::OverflowPayment => Self::Halt(Halt::OverflowPayment), // Check for first call is done separately.
-            InstructionResult::PrecompileError => Self::Halt(Halt::PrecompileError),
+            InstructionResult::PrecompileError => Self::PrecompileError,
             InstructionResult::NonceOverflow => Self::Halt(Halt::NonceOverflow),
             InstructionResult::CreateContractSizeLimit => Self::Halt(Halt::CreateContractSizeLimit),
             InstructionResult::CreateContractStartingWithEF => {
                 Self::Halt(Halt::CreateContractSizeLimit)
             }
             InstructionResult::CreateInitcodeSizeLimit => Self::Halt(Halt::CreateInitcodeSizeLimit),
+            InstructionResult::StepLimitExceeded => Self::Halt(Halt::ExecutionLimit(
+                revm_primitives::ExecutionLimitError::Steps,
+            )),
+            InstructionResult::TimeLimitExceeded => Self::Halt(Halt::ExecutionLimit(
+                revm_primitives::ExecutionLimitError::Time,
+            )),
+            InstructionResult::MemoryBudgetExceeded => Self::Halt(Halt::ExecutionLimit(
+                revm_primitives::ExecutionLimitError::Memory,
+            )),
             InstructionResult::FatalExternalError => Self::FatalExternalError,
         }
     }
@@ -13,6 +13,8 @@ mod system;
 
 use crate::{interpreter::Interpreter, primitives::Spec, Host};
 
+pub(crate) use host::{insert_call_outcome, insert_create_outcome};
+
 pub use crate::InstructionResult;
 pub fn return_stop(interpreter: &mut Interpreter, _host: &mut dyn Host) {
     interpreter.instruction_result = InstructionResult::Stop;
@@ -1,12 +1,12 @@
 use crate::primitives::{Bytes, Spec, SpecId::*, B176, B256, U256};
 use crate::{
-    alloc::vec::Vec,
-    energy::{self},
+    alloc::{boxed::Box, vec::Vec},
+    energy::{self, Energy},
     interpreter::Interpreter,
     return_ok, return_revert, CallContext, CallInputs, CallScheme, CreateInputs, CreateScheme,
-    Host, InstructionResult, Transfer,
+    Host, InstructionResult, InterpreterAction, Transfer,
 };
-use core::cmp::min;
+use core::{cmp::min, ops::Range};
 
 pub fn balance<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Host) {
     pop_address!(interpreter, address);
@@ -209,7 +209,7 @@ pub fn selfdestruct<SPEC: Spec>(interpreter: &mut Interpreter, host: &mut dyn Ho
 
 pub fn create<const IS_CREATE2: bool, SPEC: Spec>(
     interpreter: &mut Interpreter,
-    host: &mut dyn Host,
+    _host: &mut dyn Host,
 ) {
     check_staticcall!(interpreter);
     if IS_CREATE2 {
@@ -252,7 +252,7 @@ pub fn create<const IS_CREATE2: bool, SPEC: Spec>(
     }
     energy!(interpreter, energy_limit);
 
-    let mut create_input = CreateInputs {
+    let create_input = CreateInputs {
         caller: interpreter.contract.address,
         scheme,
         value,
@@ -260,7 +260,22 @@ pub fn create<const IS_CREATE2: bool, SPEC: Spec>(
         energy_limit,
     };
 
-    let (return_reason, address, energy, return_data) = host.create(&mut create_input);
+    // Suspend, host executes the create and resumes us with `insert_create_outcome`.
+    interpreter.next_action = Some(InterpreterAction::Create {
+        inputs: Box::new(create_input),
+    });
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
+
+/// Apply result of the create requested by `CREATE`/`CREATE2` to the interpreter that requested it.
+pub fn insert_create_outcome(
+    interpreter: &mut Interpreter,
+    return_reason: InstructionResult,
+    address: Option<B176>,
+    energy: Energy,
+    return_data: Bytes,
+) {
+    interpreter.instruction_result = InstructionResult::Continue;
     interpreter.return_data_buffer = match return_reason {
         // Save data to return data buffer if the create reverted
         return_revert!() => return_data,
@@ -446,7 +461,7 @@ pub fn call_inner<SPEC: Spec>(
     }
     let is_static = matches!(scheme, CallScheme::StaticCall) || interpreter.is_static;
 
-    let mut call_input = CallInputs {
+    let call_input = CallInputs {
         contract: to,
         transfer,
         input,
@@ -455,12 +470,31 @@ pub fn call_inner<SPEC: Spec>(
         is_static,
     };
 
-    // Call host to interuct with target contract
-    let (reason, energy, return_data) = host.call(&mut call_input);
+    // Suspend, host executes the call and resumes us with `insert_call_outcome`.
+    interpreter.next_action = Some(InterpreterAction::Call {
+        inputs: Box::new(call_input),
+        return_memory_offset: out_offset..out_offset.saturating_add(out_len),
+    });
+    interpreter.instruction_result = InstructionResult::CallOrCreate;
+}
 
+/// Apply result of the call requested by `CALL`-like instruction to the interpreter that requested it.
+/// `return_memory_offset` is the output memory range the caller asked for.
+pub fn insert_call_outcome(
+    interpreter: &mut Interpreter,
+    reason: InstructionResult,
+    energy: Energy,
+    return_data: Bytes,
+    return_memory_offset: Range<usize>,
+) {
+    interpreter.instruction_result = InstructionResult::Continue;
     interpreter.return_data_buffer = return_data;
 
-    let target_len = min(out_len, interpreter.return_data_buffer.len());
+    let out_offset = return_memory_offset.start;
+    let target_len = min(
+        return_memory_offset.len(),
+        interpreter.return_data_buffer.len(),
+    );
 
     match reason {
         return_ok!() => {
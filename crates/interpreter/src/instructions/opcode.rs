@@ -856,6 +856,18 @@ pub const fn spec_opcode_energy(spec_id: SpecId) -> &'static [OpInfo; 256] {
             energy_opcodee!(ISTANBUL, SpecId::ISTANBUL);
             ISTANBUL
         }
+        SpecId::CVM_ED448 => {
+            energy_opcodee!(CVM_ED448, SpecId::CVM_ED448);
+            CVM_ED448
+        }
+        SpecId::CVM_MODEXP => {
+            energy_opcodee!(CVM_MODEXP, SpecId::CVM_MODEXP);
+            CVM_MODEXP
+        }
+        SpecId::CVM_BLS => {
+            energy_opcodee!(CVM_BLS, SpecId::CVM_BLS);
+            CVM_BLS
+        }
         SpecId::LATEST => {
             energy_opcodee!(LATEST, SpecId::LATEST);
             LATEST
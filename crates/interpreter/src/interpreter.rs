@@ -8,10 +8,10 @@ pub use contract::Contract;
 pub use memory::Memory;
 pub use stack::Stack;
 
-use crate::primitives::{Bytes, Spec};
+use crate::primitives::{Bytes, Spec, B176};
 use crate::{
-    instructions::{eval, InstructionResult},
-    Energy, Host,
+    instructions::{eval, insert_call_outcome, insert_create_outcome, InstructionResult},
+    Energy, Host, InterpreterAction,
 };
 use core::ops::Range;
 
@@ -43,11 +43,18 @@ pub struct Interpreter {
     pub is_static: bool,
     /// Contract information and invoking data
     pub contract: Contract,
+    /// Call or create waiting to be executed by the host,
+    /// set when interpreter is suspended with [InstructionResult::CallOrCreate].
+    pub next_action: Option<InterpreterAction>,
     /// Memory limit. See [`crate::CfgEnv`].
     #[cfg(feature = "memory_limit")]
     pub memory_limit: u64,
 }
 
+// SAFETY: `instruction_pointer` points into the bytecode owned by `contract`, bytecode is
+// allocated on heap and it is moved together with the interpreter.
+unsafe impl Send for Interpreter {}
+
 impl Interpreter {
     /// Current opcode
     pub fn current_opcode(&self) -> u8 {
@@ -65,6 +72,7 @@ impl Interpreter {
                 stack: Stack::new(),
                 return_data_buffer: Bytes::new(),
                 contract,
+                next_action: None,
                 instruction_result: InstructionResult::Continue,
                 is_static,
                 energy: Energy::new(energy_limit),
@@ -91,6 +99,7 @@ impl Interpreter {
             stack: Stack::new(),
             return_data_buffer: Bytes::new(),
             contract,
+            next_action: None,
             instruction_result: InstructionResult::Continue,
             is_static,
             energy: Energy::new(energy_limit),
@@ -154,6 +163,10 @@ impl Interpreter {
                 return ret;
             }
             self.step::<H, SPEC>(host);
+            // step end of call or create is called by host after it resumes us.
+            if self.instruction_result == InstructionResult::CallOrCreate {
+                break;
+            }
 
             // step ends
             let ret = host.step_end(self, self.is_static, self.instruction_result);
@@ -164,6 +177,28 @@ impl Interpreter {
         self.instruction_result
     }
 
+    /// Resume interpreter suspended by `CALL`-like instruction with the outcome of the call.
+    pub fn insert_call_outcome(
+        &mut self,
+        ret: InstructionResult,
+        energy: Energy,
+        out: Bytes,
+        return_memory_offset: Range<usize>,
+    ) {
+        insert_call_outcome(self, ret, energy, out, return_memory_offset)
+    }
+
+    /// Resume interpreter suspended by `CREATE` or `CREATE2` with the outcome of the create.
+    pub fn insert_create_outcome(
+        &mut self,
+        ret: InstructionResult,
+        address: Option<B176>,
+        energy: Energy,
+        out: Bytes,
+    ) {
+        insert_create_outcome(self, ret, address, energy, out)
+    }
+
     /// Copy and get the return value of the interpreter, if any.
     pub fn return_value(&self) -> Bytes {
         // if start is usize max it means that our return len is zero and we need to return empty
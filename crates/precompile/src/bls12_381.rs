@@ -0,0 +1,566 @@
+//! BLS12-381 curve operations, see: https://eips.ethereum.org/EIPS/eip-2537
+//!
+//! Field elements are encoded as 64 bytes, big endian number padded by 16 zero bytes. `Fp2`
+//! element is encoded as `c0 | c1`, G1 point as `x | y` and G2 point as `x | y` of `Fp2`
+//! coordinates. Point at infinity is encoded with all zero coordinates.
+use crate::{Error, Precompile, PrecompileAddress, PrecompileResult, StandardPrecompileFn};
+use bls::{
+    hash_to_curve::MapToCurve, multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared,
+    G2Projective, Gt, Scalar,
+};
+use revm_primitives::Network;
+
+/// Base field element, the type is reachable only through the map to curve trait.
+type Fp = <G1Projective as MapToCurve>::Field;
+/// Quadratic extension of the base field.
+type Fp2 = <G2Projective as MapToCurve>::Field;
+
+pub mod g1_add {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x0b),
+        Precompile::Standard(g1_add_run as StandardPrecompileFn),
+    );
+}
+
+pub mod g1_msm {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x0c),
+        Precompile::Standard(g1_msm_run as StandardPrecompileFn),
+    );
+}
+
+pub mod g2_add {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x0d),
+        Precompile::Standard(g2_add_run as StandardPrecompileFn),
+    );
+}
+
+pub mod g2_msm {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x0e),
+        Precompile::Standard(g2_msm_run as StandardPrecompileFn),
+    );
+}
+
+pub mod pairing {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x0f),
+        Precompile::Standard(pairing_run as StandardPrecompileFn),
+    );
+}
+
+pub mod map_fp_to_g1 {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x10),
+        Precompile::Standard(map_fp_to_g1_run as StandardPrecompileFn),
+    );
+}
+
+pub mod map_fp2_to_g2 {
+    use super::*;
+
+    pub const FUN: PrecompileAddress = PrecompileAddress(
+        crate::u64_to_b176(0x11),
+        Precompile::Standard(map_fp2_to_g2_run as StandardPrecompileFn),
+    );
+}
+
+/// Length of the field element without padding.
+const FP_LENGTH: usize = 48;
+/// Length of the encoded field element.
+const PADDED_FP_LENGTH: usize = 64;
+/// Length of the encoded G1 point.
+const G1_LENGTH: usize = 2 * PADDED_FP_LENGTH;
+/// Length of the encoded `Fp2` element.
+const PADDED_FP2_LENGTH: usize = 2 * PADDED_FP_LENGTH;
+/// Length of the encoded G2 point.
+const G2_LENGTH: usize = 2 * PADDED_FP2_LENGTH;
+/// Length of the scalar, it is not required to be smaller than the group order.
+const SCALAR_LENGTH: usize = 32;
+/// Length of one G1 multi-scalar multiplication pair.
+const G1_MSM_PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+/// Length of one G2 multi-scalar multiplication pair.
+const G2_MSM_PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+/// Length of one pairing check pair.
+const PAIRING_PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+
+const G1_ADD_COST: u64 = 375;
+const G2_ADD_COST: u64 = 600;
+const G1_MUL_COST: u64 = 12_000;
+const G2_MUL_COST: u64 = 22_500;
+const PAIRING_PER_PAIR_COST: u64 = 32_600;
+const PAIRING_BASE_COST: u64 = 37_700;
+const MAP_FP_TO_G1_COST: u64 = 5_500;
+const MAP_FP2_TO_G2_COST: u64 = 23_800;
+
+/// Multiplier of the multi-scalar multiplication discounts.
+const MSM_DISCOUNT_MULTIPLIER: u64 = 1_000;
+
+/// Discounts of G1 multi-scalar multiplication by number of pairs, last one applies to all
+/// longer inputs.
+const G1_MSM_DISCOUNTS: [u16; 128] = [
+    1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665,
+    661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615,
+    613, 611, 609, 608, 606, 604, 603, 601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585,
+    584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564, 563,
+    562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547, 547, 546, 545,
+    544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529,
+    528, 528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Discounts of G2 multi-scalar multiplication by number of pairs, last one applies to all
+/// longer inputs.
+const G2_MSM_DISCOUNTS: [u16; 128] = [
+    1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704,
+    699, 693, 688, 683, 679, 674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632,
+    629, 627, 624, 622, 620, 618, 615, 613, 611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593,
+    592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576, 575, 574, 573, 571, 570, 569, 568,
+    567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552, 551, 550, 549,
+    548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534,
+    533, 532, 532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+fn g1_add_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if G1_ADD_COST > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+    if input.len() != 2 * G1_LENGTH {
+        return Err(Error::Bls12381InputLength);
+    }
+    // addition is defined for all points on the curve, subgroup is not checked.
+    let a = read_g1(&input[..G1_LENGTH], false)?;
+    let b = read_g1(&input[G1_LENGTH..], false)?;
+    let sum = G1Projective::from(a) + b;
+    Ok((G1_ADD_COST, encode_g1(sum.into())))
+}
+
+fn g2_add_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if G2_ADD_COST > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+    if input.len() != 2 * G2_LENGTH {
+        return Err(Error::Bls12381InputLength);
+    }
+    let a = read_g2(&input[..G2_LENGTH], false)?;
+    let b = read_g2(&input[G2_LENGTH..], false)?;
+    let sum = G2Projective::from(a) + b;
+    Ok((G2_ADD_COST, encode_g2(sum.into())))
+}
+
+fn g1_msm_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if input.is_empty() || !input.len().is_multiple_of(G1_MSM_PAIR_LENGTH) {
+        return Err(Error::Bls12381InputLength);
+    }
+    let pairs = input.len() / G1_MSM_PAIR_LENGTH;
+    let energy_used = msm_cost(pairs, G1_MUL_COST, &G1_MSM_DISCOUNTS);
+    if energy_used > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+
+    let mut result = G1Projective::identity();
+    for pair in input.chunks_exact(G1_MSM_PAIR_LENGTH) {
+        let point = read_g1(&pair[..G1_LENGTH], true)?;
+        result += G1Projective::from(point) * read_scalar(&pair[G1_LENGTH..]);
+    }
+    Ok((energy_used, encode_g1(result.into())))
+}
+
+fn g2_msm_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if input.is_empty() || !input.len().is_multiple_of(G2_MSM_PAIR_LENGTH) {
+        return Err(Error::Bls12381InputLength);
+    }
+    let pairs = input.len() / G2_MSM_PAIR_LENGTH;
+    let energy_used = msm_cost(pairs, G2_MUL_COST, &G2_MSM_DISCOUNTS);
+    if energy_used > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+
+    let mut result = G2Projective::identity();
+    for pair in input.chunks_exact(G2_MSM_PAIR_LENGTH) {
+        let point = read_g2(&pair[..G2_LENGTH], true)?;
+        result += G2Projective::from(point) * read_scalar(&pair[G2_LENGTH..]);
+    }
+    Ok((energy_used, encode_g2(result.into())))
+}
+
+/// Returns 1 as 32 byte word if product of pairings of all pairs is the identity, 0 otherwise.
+fn pairing_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if input.is_empty() || !input.len().is_multiple_of(PAIRING_PAIR_LENGTH) {
+        return Err(Error::Bls12381InputLength);
+    }
+    let pairs = (input.len() / PAIRING_PAIR_LENGTH) as u64;
+    let energy_used = PAIRING_PER_PAIR_COST * pairs + PAIRING_BASE_COST;
+    if energy_used > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+
+    let mut terms = Vec::new();
+    for pair in input.chunks_exact(PAIRING_PAIR_LENGTH) {
+        let p = read_g1(&pair[..G1_LENGTH], true)?;
+        let q = read_g2(&pair[G1_LENGTH..], true)?;
+        // pairing with the point at infinity is the identity.
+        if !bool::from(p.is_identity() | q.is_identity()) {
+            terms.push((p, G2Prepared::from(q)));
+        }
+    }
+    let terms: Vec<_> = terms.iter().map(|(p, q)| (p, q)).collect();
+    let success = multi_miller_loop(&terms).final_exponentiation() == Gt::identity();
+
+    let mut output = vec![0u8; 32];
+    output[31] = success as u8;
+    Ok((energy_used, output))
+}
+
+fn map_fp_to_g1_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if MAP_FP_TO_G1_COST > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+    if input.len() != PADDED_FP_LENGTH {
+        return Err(Error::Bls12381InputLength);
+    }
+    let fp = read_fp(input)?;
+    let point = G1Projective::map_to_curve(&fp).clear_h();
+    Ok((MAP_FP_TO_G1_COST, encode_g1(point.into())))
+}
+
+fn map_fp2_to_g2_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if MAP_FP2_TO_G2_COST > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+    if input.len() != PADDED_FP2_LENGTH {
+        return Err(Error::Bls12381InputLength);
+    }
+    let fp2 = Fp2 {
+        c0: read_fp(&input[..PADDED_FP_LENGTH])?,
+        c1: read_fp(&input[PADDED_FP_LENGTH..])?,
+    };
+    let point = G2Projective::map_to_curve(&fp2).clear_h();
+    Ok((MAP_FP2_TO_G2_COST, encode_g2(point.into())))
+}
+
+/// Energy of multi-scalar multiplication of `pairs` points.
+fn msm_cost(pairs: usize, mul_cost: u64, discounts: &[u16; 128]) -> u64 {
+    let discount = discounts[pairs.min(discounts.len()) - 1] as u64;
+    pairs as u64 * mul_cost * discount / MSM_DISCOUNT_MULTIPLIER
+}
+
+/// Strips the padding of the field element and checks that it is smaller than the modulus.
+fn remove_padding(input: &[u8]) -> Result<[u8; FP_LENGTH], Error> {
+    let (padding, element) = input.split_at(PADDED_FP_LENGTH - FP_LENGTH);
+    if padding.iter().any(|&byte| byte != 0) {
+        return Err(Error::Bls12381FpPaddingInvalid);
+    }
+    let element: [u8; FP_LENGTH] = element.try_into().unwrap();
+    if bool::from(Fp::from_bytes(&element).is_none()) {
+        return Err(Error::Bls12381FpNotInField);
+    }
+    Ok(element)
+}
+
+fn read_fp(input: &[u8]) -> Result<Fp, Error> {
+    // element is already checked to be in the field.
+    Ok(Fp::from_bytes(&remove_padding(input)?).unwrap())
+}
+
+/// Scalar is reduced by the group order, all points it multiplies are in the subgroup.
+fn read_scalar(input: &[u8]) -> Scalar {
+    let mut wide = [0u8; 64];
+    for (byte, &value) in wide.iter_mut().zip(input.iter().rev()) {
+        *byte = value;
+    }
+    Scalar::from_bytes_wide(&wide)
+}
+
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, Error> {
+    let mut bytes = [0u8; 2 * FP_LENGTH];
+    bytes[..FP_LENGTH].copy_from_slice(&remove_padding(&input[..PADDED_FP_LENGTH])?);
+    bytes[FP_LENGTH..].copy_from_slice(&remove_padding(&input[PADDED_FP_LENGTH..])?);
+    if bytes.iter().all(|&byte| byte == 0) {
+        return Ok(G1Affine::identity());
+    }
+    // flag bits are zero as coordinates are smaller than the modulus.
+    let point = G1Affine::from_uncompressed_unchecked(&bytes).unwrap();
+    if !bool::from(point.is_on_curve()) {
+        return Err(Error::Bls12381PointNotOnCurve);
+    }
+    if subgroup_check && !bool::from(point.is_torsion_free()) {
+        return Err(Error::Bls12381PointNotInSubgroup);
+    }
+    Ok(point)
+}
+
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, Error> {
+    // serialized `Fp2` coordinates start with `c1`.
+    let mut bytes = [0u8; 4 * FP_LENGTH];
+    for (i, element) in input.chunks_exact(PADDED_FP2_LENGTH).enumerate() {
+        let at = 2 * FP_LENGTH * i;
+        bytes[at..at + FP_LENGTH].copy_from_slice(&remove_padding(&element[PADDED_FP_LENGTH..])?);
+        bytes[at + FP_LENGTH..at + 2 * FP_LENGTH]
+            .copy_from_slice(&remove_padding(&element[..PADDED_FP_LENGTH])?);
+    }
+    if bytes.iter().all(|&byte| byte == 0) {
+        return Ok(G2Affine::identity());
+    }
+    let point = G2Affine::from_uncompressed_unchecked(&bytes).unwrap();
+    if !bool::from(point.is_on_curve()) {
+        return Err(Error::Bls12381PointNotOnCurve);
+    }
+    if subgroup_check && !bool::from(point.is_torsion_free()) {
+        return Err(Error::Bls12381PointNotInSubgroup);
+    }
+    Ok(point)
+}
+
+fn encode_g1(point: G1Affine) -> Vec<u8> {
+    let mut output = vec![0u8; G1_LENGTH];
+    if bool::from(point.is_identity()) {
+        return output;
+    }
+    let bytes = point.to_uncompressed();
+    for (element, bytes) in output
+        .chunks_exact_mut(PADDED_FP_LENGTH)
+        .zip(bytes.chunks_exact(FP_LENGTH))
+    {
+        element[PADDED_FP_LENGTH - FP_LENGTH..].copy_from_slice(bytes);
+    }
+    output
+}
+
+fn encode_g2(point: G2Affine) -> Vec<u8> {
+    let mut output = vec![0u8; G2_LENGTH];
+    if bool::from(point.is_identity()) {
+        return output;
+    }
+    let bytes = point.to_uncompressed();
+    for (element, bytes) in output
+        .chunks_exact_mut(PADDED_FP2_LENGTH)
+        .zip(bytes.chunks_exact(2 * FP_LENGTH))
+    {
+        element[PADDED_FP_LENGTH - FP_LENGTH..PADDED_FP_LENGTH]
+            .copy_from_slice(&bytes[FP_LENGTH..]);
+        element[2 * PADDED_FP_LENGTH - FP_LENGTH..].copy_from_slice(&bytes[..FP_LENGTH]);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const G1: &str = "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+    const NEG_G1: &str = "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb00000000000000000000000000000000114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca";
+    const G1_DOUBLE: &str = "000000000000000000000000000000000572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e00000000000000000000000000000000166a9d8cabc673a322fda673779d8e3822ba3ecb8670e461f73bb9021d5fd76a4c56d9d4cd16bd1bba86881979749d28";
+    const P1: &str = "00000000000000000000000000000000112b98340eee2777cc3c14163dea3ec97977ac3dc5c70da32e6e87578f44912e902ccef9efe28d4a78b8999dfbca942600000000000000000000000000000000186b28d92356c4dfec4b5201ad099dbdede3781f8998ddf929b4cd7756192185ca7b8f4ef7088f813270ac3d48868a21";
+    const G2: &str = "00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be";
+    const G2_DOUBLE: &str = "000000000000000000000000000000001638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053000000000000000000000000000000000a4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c33577000000000000000000000000000000000468fb440d82b0630aeb8dca2b5256789a66da69bf91009cbfe6bd221e47aa8ae88dece9764bf3bd999d95d71e4c9899000000000000000000000000000000000f6d4552fa65dd2638b361543f887136a43253d9c66c411697003f7a13c308f5422e1aa0a59c8967acdefd8b6e36ccf3";
+    /// Order of the G1 and G2 subgroups.
+    const R: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+    fn input(parts: &[&str]) -> Vec<u8> {
+        hex::decode(parts.concat()).unwrap()
+    }
+
+    #[test]
+    fn test_g1_add() {
+        let (energy_used, out) = g1_add_run(&input(&[G1, P1]), 375, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, 375);
+        assert_eq!(out, input(&["000000000000000000000000000000000a40300ce2dec9888b60690e9a41d3004fda4886854573974fab73b046d3147ba5b7a5bde85279ffede1b45b3918d82d0000000000000000000000000000000006d3d887e9f53b9ec4eb6cedf5607226754b07c01ace7834f57f3e7315faefb739e59018e22c492006190fba4a870025"]));
+
+        let infinity = "00".repeat(G1_LENGTH);
+        let (_, out) = g1_add_run(&input(&[G1, &infinity]), 375, Network::Mainnet).unwrap();
+        assert_eq!(out, input(&[G1]));
+        let (_, out) = g1_add_run(&input(&[G1, NEG_G1]), 375, Network::Mainnet).unwrap();
+        assert_eq!(out, input(&[&infinity]));
+
+        assert_eq!(
+            g1_add_run(&input(&[G1, P1]), 374, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+        assert_eq!(
+            g1_add_run(&input(&[G1]), 375, Network::Mainnet),
+            Err(Error::Bls12381InputLength)
+        );
+    }
+
+    #[test]
+    fn test_g1_msm() {
+        let (energy_used, out) = g1_msm_run(&input(&[G1, R]), 12_000, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, 12_000);
+        assert_eq!(out, vec![0; G1_LENGTH]);
+
+        assert_eq!(
+            g1_msm_run(&input(&[G1, R]), 11_999, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+        assert_eq!(
+            g1_msm_run(&[], 100_000, Network::Mainnet),
+            Err(Error::Bls12381InputLength)
+        );
+        assert_eq!(
+            msm_cost(200, G1_MUL_COST, &G1_MSM_DISCOUNTS),
+            200 * 12 * 519
+        );
+    }
+
+    #[test]
+    fn test_g2_add() {
+        let (energy_used, out) =
+            g2_add_run(&input(&[G2, G2_DOUBLE]), 600, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, 600);
+        assert_eq!(out, input(&["00000000000000000000000000000000122915c824a0857e2ee414a3dccb23ae691ae54329781315a0c75df1c04d6d7a50a030fc866f09d516020ef82324afae0000000000000000000000000000000009380275bbc8e5dcea7dc4dd7e0550ff2ac480905396eda55062650f8d251c96eb480673937cc6d9d6a44aaa56ca66dc000000000000000000000000000000000b21da7955969e61010c7a1abc1a6f0136961d1e3b20b1a7326ac738fef5c721479dfd948b52fdf2455e44813ecfd8920000000000000000000000000000000008f239ba329b3967fe48d718a36cfe5f62a7e42e0bf1c1ed714150a166bfbd6bcf6b3b58b975b9edea56d53f23a0e849"]));
+
+        let (_, out) = g2_add_run(&input(&[G2, G2]), 600, Network::Mainnet).unwrap();
+        assert_eq!(out, input(&[G2_DOUBLE]));
+        assert_eq!(
+            g2_add_run(&input(&[G2, G2]), 599, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+    }
+
+    #[test]
+    fn test_g2_msm() {
+        let (energy_used, out) = g2_msm_run(&input(&[G2, R]), 22_500, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, 22_500);
+        assert_eq!(out, vec![0; G2_LENGTH]);
+
+        assert_eq!(
+            g2_msm_run(&input(&[G2, R]), 22_499, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+    }
+
+    #[test]
+    fn test_pairing() {
+        let one = input(&["0000000000000000000000000000000000000000000000000000000000000001"]);
+        let (energy_used, out) =
+            pairing_run(&input(&[G1, G2, NEG_G1, G2]), 102_900, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, 2 * 32_600 + 37_700);
+        assert_eq!(out, one);
+
+        let (_, out) = pairing_run(
+            &input(&[G1_DOUBLE, G2, NEG_G1, G2_DOUBLE]),
+            102_900,
+            Network::Mainnet,
+        )
+        .unwrap();
+        assert_eq!(out, one);
+
+        let (_, out) = pairing_run(&input(&[G1, G2]), 70_300, Network::Mainnet).unwrap();
+        assert_eq!(out, vec![0; 32]);
+
+        assert_eq!(
+            pairing_run(&input(&[G1, G2]), 70_299, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+        assert_eq!(
+            pairing_run(&[], 100_000, Network::Mainnet),
+            Err(Error::Bls12381InputLength)
+        );
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        // point on curve, but not in the subgroup.
+        let not_in_subgroup = "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c";
+        assert!(g1_add_run(&input(&[not_in_subgroup, G1]), 375, Network::Mainnet).is_ok());
+        assert_eq!(
+            g1_msm_run(&input(&[not_in_subgroup, R]), 12_000, Network::Mainnet),
+            Err(Error::Bls12381PointNotInSubgroup)
+        );
+
+        let mut not_on_curve = input(&[G1]);
+        not_on_curve[G1_LENGTH - 1] ^= 1;
+        assert_eq!(
+            g1_add_run(
+                &[not_on_curve, input(&[G1])].concat(),
+                375,
+                Network::Mainnet
+            ),
+            Err(Error::Bls12381PointNotOnCurve)
+        );
+
+        let mut padding = input(&[G1, G1]);
+        padding[0] = 1;
+        assert_eq!(
+            g1_add_run(&padding, 375, Network::Mainnet),
+            Err(Error::Bls12381FpPaddingInvalid)
+        );
+
+        // field modulus.
+        let modulus = "000000000000000000000000000000001a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+        assert_eq!(
+            map_fp_to_g1_run(&input(&[modulus]), 5_500, Network::Mainnet),
+            Err(Error::Bls12381FpNotInField)
+        );
+    }
+
+    /// Runs vectors in the EIP-2537 JSON format. `fail-` files have no expected output, their
+    /// inputs must be rejected. `Gas` is not checked, upstream files predate the final prices,
+    /// which are covered by the tests above.
+    fn run_vectors(file: &str, vectors: &str, run: StandardPrecompileFn) {
+        let vectors: serde_json::Value = serde_json::from_str(vectors).unwrap();
+        for vector in vectors.as_array().unwrap() {
+            let name = format!("{file}: {}", vector["Name"].as_str().unwrap());
+            let input = hex::decode(vector["Input"].as_str().unwrap()).unwrap();
+            let result = run(&input, u64::MAX, Network::Mainnet);
+            match vector.get("Expected") {
+                Some(expected) => {
+                    let expected = hex::decode(expected.as_str().unwrap()).unwrap();
+                    assert_eq!(result.map(|(_, out)| out), Ok(expected), "{name}");
+                }
+                None => assert!(result.is_err(), "{name}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_eip2537_vectors() {
+        macro_rules! vectors {
+            ($($file:literal => $run:ident),* $(,)?) => {
+                [$((
+                    $file,
+                    include_str!(concat!("../test-vectors/eip2537/", $file)),
+                    $run as StandardPrecompileFn,
+                )),*]
+            };
+        }
+        for (file, vectors, run) in vectors![
+            "add_G1_bls.json" => g1_add_run,
+            "fail-add_G1_bls.json" => g1_add_run,
+            "add_G2_bls.json" => g2_add_run,
+            "fail-add_G2_bls.json" => g2_add_run,
+            // single pair msm, the separate mul precompiles were merged into msm.
+            "mul_G1_bls.json" => g1_msm_run,
+            "fail-mul_G1_bls.json" => g1_msm_run,
+            "mul_G2_bls.json" => g2_msm_run,
+            "fail-mul_G2_bls.json" => g2_msm_run,
+            "multiexp_G1_bls.json" => g1_msm_run,
+            "fail-multiexp_G1_bls.json" => g1_msm_run,
+            "multiexp_G2_bls.json" => g2_msm_run,
+            "fail-multiexp_G2_bls.json" => g2_msm_run,
+            "pairing_check_bls.json" => pairing_run,
+            "fail-pairing_check_bls.json" => pairing_run,
+            "map_fp_to_G1_bls.json" => map_fp_to_g1_run,
+            "fail-map_fp_to_G1_bls.json" => map_fp_to_g1_run,
+            "map_fp2_to_G2_bls.json" => map_fp2_to_g2_run,
+            "fail-map_fp2_to_G2_bls.json" => map_fp2_to_g2_run,
+            "map_fp_to_G1_rfc9380.json" => map_fp_to_g1_run,
+            "map_fp2_to_G2_rfc9380.json" => map_fp2_to_g2_run,
+        ] {
+            run_vectors(file, vectors, run);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use super::calc_linear_cost_u32;
+use crate::{Error, Precompile, PrecompileAddress, PrecompileResult, StandardPrecompileFn};
+use libgoldilocks::goldilocks::ed448_verify_with_error;
+use revm_primitives::{pubkey_to_address, Network, B256};
+
+pub const VERIFY: PrecompileAddress = PrecompileAddress(
+    crate::u64_to_b176(0x20),
+    Precompile::Standard(verify_run as StandardPrecompileFn),
+);
+pub const TO_ICAN: PrecompileAddress = PrecompileAddress(
+    crate::u64_to_b176(0x22),
+    Precompile::Standard(to_ican_run as StandardPrecompileFn),
+);
+
+const PUBLIC_KEY_LENGTH: usize = 57;
+const SIGNATURE_LENGTH: usize = 114;
+/// The base cost of the operation.
+const VERIFY_BASE: u64 = 3_000;
+/// The cost per word of the message.
+const VERIFY_PER_WORD: u64 = 12;
+/// The base cost of address derivation, priced as SHA256 of the public key as the SHA3-256
+/// hash dominates the checksum calculation.
+const TO_ICAN_BASE: u64 = 60;
+/// The cost per word of the public key.
+const TO_ICAN_PER_WORD: u64 = 12;
+
+/// Verifies Ed448 signature of an arbitrary message, unlike `ecrecover` the public key is given
+/// and nothing is derived from it.
+///
+/// input format:
+/// [57 bytes for public key][114 bytes for signature][message]
+/// output is a 32 byte word, 1 if the signature is valid and 0 otherwise.
+fn verify_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    if input.len() < PUBLIC_KEY_LENGTH + SIGNATURE_LENGTH {
+        return Err(Error::Ed448VerifyInputLength);
+    }
+    let (public_key, rest) = input.split_at(PUBLIC_KEY_LENGTH);
+    let (signature, message) = rest.split_at(SIGNATURE_LENGTH);
+
+    let energy_used = calc_linear_cost_u32(message.len(), VERIFY_BASE, VERIFY_PER_WORD);
+    if energy_used > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+
+    let public_key: [u8; PUBLIC_KEY_LENGTH] = public_key.try_into().unwrap();
+    let signature: [u8; SIGNATURE_LENGTH] = signature.try_into().unwrap();
+    let mut out = [0u8; 32];
+    out[31] = ed448_verify_with_error(&public_key, &signature, message).is_ok() as u8;
+    Ok((energy_used, out.to_vec()))
+}
+
+/// Derives the ICAN address of the account controlled by a raw Ed448 public key, the same way
+/// `ecrecover` does after verifying the signature.
+///
+/// input format:
+/// [57 bytes for public key]
+/// output is the address as a 32 byte word.
+fn to_ican_run(input: &[u8], energy_limit: u64, network: Network) -> PrecompileResult {
+    let energy_used = calc_linear_cost_u32(input.len(), TO_ICAN_BASE, TO_ICAN_PER_WORD);
+    if energy_used > energy_limit {
+        return Err(Error::OutOfEnergy);
+    }
+    if input.len() != PUBLIC_KEY_LENGTH {
+        return Err(Error::Ed448PublicKeyLength);
+    }
+    let address = B256::from(pubkey_to_address(input, network));
+    Ok((energy_used, address.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // signature and public key of the `ecrecover` test vector.
+    const SIGNATURE: &str = "611d178b128095022653965eb0ed3bc8bbea8e7891b5a121a102a5b29bb895770d204354dbbc67c5567186f92cdb58a601397dfe0022e0ce002c1333b6829c37c732fb909501f719df200ceaaa0e0a1533dc22e4c9c999406c071fee2858bc7c76c66d113ff1ac739564d465cd541b0d1e003761457fcdd53dba3dea5848c43aa54fe468284319f032945a3acb9bd4cd0fa7b7c901d978e9acd9eca43fa5b3c32b648c33dcc3f3169e8080";
+    const MESSAGE: &str = "f092a4af1f2103fe7be067df44370097c444f3bf877783ba56f21cf70ba365a3";
+
+    fn input() -> Vec<u8> {
+        let sig = hex::decode(SIGNATURE).unwrap();
+        let mut input = sig[114..].to_vec();
+        input.extend_from_slice(&sig[..114]);
+        input.extend_from_slice(&hex::decode(MESSAGE).unwrap());
+        input
+    }
+
+    #[test]
+    fn test_verify() {
+        let (energy_used, out) = verify_run(&input(), 10_000, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, VERIFY_BASE + VERIFY_PER_WORD);
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        let input = input();
+        assert_eq!(
+            verify_run(&input, VERIFY_BASE, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+        assert_eq!(
+            verify_run(&input[..170], 10_000, Network::Mainnet),
+            Err(Error::Ed448VerifyInputLength)
+        );
+    }
+
+    #[test]
+    fn test_to_ican() {
+        let sig = hex::decode(SIGNATURE).unwrap();
+        let (energy_used, out) = to_ican_run(&sig[114..], 100, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, TO_ICAN_BASE + 2 * TO_ICAN_PER_WORD);
+        assert_eq!(
+            out,
+            hex::decode("00000000000000000000cb58fc37a3b370a1f22e2fe2f819c210895e098845ed")
+                .unwrap()
+        );
+        assert_eq!(
+            to_ican_run(&sig[113..], 100, Network::Mainnet),
+            Err(Error::Ed448PublicKeyLength)
+        );
+        assert_eq!(
+            to_ican_run(&sig[114..], 83, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+    }
+}
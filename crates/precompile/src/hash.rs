@@ -11,6 +11,10 @@ pub const RIPEMD160: PrecompileAddress = PrecompileAddress(
     crate::u64_to_b176(3),
     Precompile::Standard(ripemd160_run as StandardPrecompileFn),
 );
+pub const SHA3_512: PrecompileAddress = PrecompileAddress(
+    crate::u64_to_b176(0x21),
+    Precompile::Standard(sha3_512_run as StandardPrecompileFn),
+);
 
 /// See: https://ethereum.github.io/yellowpaper/paper.pdf
 /// See: https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions
@@ -38,3 +42,39 @@ fn ripemd160_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResul
         Ok((energy_used, ret.to_vec()))
     }
 }
+
+/// The base cost of the operation, priced as SHA256 as both hashes have similar cost per word.
+const SHA3_512_BASE: u64 = 60;
+/// The cost per word of the input.
+const SHA3_512_PER_WORD: u64 = 12;
+
+/// SHA3-512 hash of the input, output is 64 bytes.
+fn sha3_512_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    let energy_used = calc_linear_cost_u32(input.len(), SHA3_512_BASE, SHA3_512_PER_WORD);
+    if energy_used > energy_limit {
+        Err(Error::OutOfEnergy)
+    } else {
+        Ok((energy_used, sha3::Sha3_512::digest(input).to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha3_512() {
+        let (energy_used, out) = sha3_512_run(b"", 100, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, SHA3_512_BASE);
+        assert_eq!(out, hex::decode("a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26").unwrap());
+
+        let (energy_used, out) = sha3_512_run(b"abc", 100, Network::Mainnet).unwrap();
+        assert_eq!(energy_used, SHA3_512_BASE + SHA3_512_PER_WORD);
+        assert_eq!(out, hex::decode("b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0").unwrap());
+
+        assert_eq!(
+            sha3_512_run(&[0u8; 33], 83, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+    }
+}
@@ -1,5 +1,7 @@
 mod blake2;
+mod bls12_381;
 mod bn128;
+mod ed448;
 mod hash;
 mod identity;
 mod modexp;
@@ -50,9 +52,10 @@ pub struct Precompiles {
     pub fun: HashMap<B176, Precompile>,
 }
 
+/// Precompiles of the [SpecId::LATEST] spec.
 impl Default for Precompiles {
     fn default() -> Self {
-        Self::new(SpecId::LATEST).clone() //berlin
+        Self::new(SpecId::LATEST).clone()
     }
 }
 
@@ -80,11 +83,15 @@ impl From<PrecompileAddress> for (B176, Precompile) {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[allow(non_camel_case_types)]
 pub enum SpecId {
     HOMESTEAD = 0,
     BYZANTIUM = 1,
     ISTANBUL = 2,
-    LATEST = 3,
+    CVM_ED448 = 3,
+    CVM_MODEXP = 4,
+    CVM_BLS = 5,
+    LATEST = 6,
 }
 
 impl SpecId {
@@ -97,6 +104,9 @@ impl SpecId {
             }
             BYZANTIUM | CONSTANTINOPLE | PETERSBURG => Self::BYZANTIUM,
             ISTANBUL => Self::ISTANBUL,
+            CVM_ED448 => Self::CVM_ED448,
+            CVM_MODEXP => Self::CVM_MODEXP,
+            CVM_BLS => Self::CVM_BLS,
             LATEST => Self::LATEST,
         }
     }
@@ -164,8 +174,65 @@ impl Precompiles {
         })
     }
 
+    pub fn cvm_ed448() -> &'static Self {
+        static INSTANCE: OnceCell<Precompiles> = OnceCell::new();
+        INSTANCE.get_or_init(|| {
+            let mut precompiles = Self::istanbul().clone();
+            precompiles.fun.extend(
+                vec![
+                    // Ed448 signature verification of arbitrary messages.
+                    ed448::VERIFY,
+                    // SHA3-512 hash and ICAN address of Ed448 public key.
+                    hash::SHA3_512,
+                    ed448::TO_ICAN,
+                ]
+                .into_iter()
+                .map(From::from),
+            );
+            precompiles
+        })
+    }
+
+    pub fn cvm_modexp() -> &'static Self {
+        static INSTANCE: OnceCell<Precompiles> = OnceCell::new();
+        INSTANCE.get_or_init(|| {
+            let mut precompiles = Self::cvm_ed448().clone();
+            precompiles.fun.extend(
+                vec![
+                    // EIP-2565: ModExp Energy Cost.
+                    modexp::CVM_MODEXP,
+                ]
+                .into_iter()
+                .map(From::from),
+            );
+            precompiles
+        })
+    }
+
+    pub fn cvm_bls() -> &'static Self {
+        static INSTANCE: OnceCell<Precompiles> = OnceCell::new();
+        INSTANCE.get_or_init(|| {
+            let mut precompiles = Self::cvm_modexp().clone();
+            precompiles.fun.extend(
+                vec![
+                    // EIP-2537: Precompile for BLS12-381 curve operations.
+                    bls12_381::g1_add::FUN,
+                    bls12_381::g1_msm::FUN,
+                    bls12_381::g2_add::FUN,
+                    bls12_381::g2_msm::FUN,
+                    bls12_381::pairing::FUN,
+                    bls12_381::map_fp_to_g1::FUN,
+                    bls12_381::map_fp2_to_g2::FUN,
+                ]
+                .into_iter()
+                .map(From::from),
+            );
+            precompiles
+        })
+    }
+
     pub fn latest() -> &'static Self {
-        Self::istanbul()
+        Self::cvm_bls()
     }
 
     pub fn new(spec: SpecId) -> &'static Self {
@@ -173,6 +240,9 @@ impl Precompiles {
             SpecId::HOMESTEAD => Self::homestead(),
             SpecId::BYZANTIUM => Self::byzantium(),
             SpecId::ISTANBUL => Self::istanbul(),
+            SpecId::CVM_ED448 => Self::cvm_ed448(),
+            SpecId::CVM_MODEXP => Self::cvm_modexp(),
+            SpecId::CVM_BLS => Self::cvm_bls(),
             SpecId::LATEST => Self::latest(),
         }
     }
@@ -209,3 +279,22 @@ const fn u64_to_b176(x: u64) -> B176 {
         x_bytes[4], x_bytes[5], x_bytes[6], x_bytes[7],
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed448_fork() {
+        for address in [ed448::VERIFY.0, hash::SHA3_512.0, ed448::TO_ICAN.0] {
+            assert!(!Precompiles::new(SpecId::ISTANBUL).contains(&address));
+            assert!(Precompiles::new(SpecId::CVM_ED448).contains(&address));
+            assert!(Precompiles::new(SpecId::LATEST).contains(&address));
+        }
+        // default is the latest spec, a precompile added to it is added to the default too.
+        assert_eq!(
+            Precompiles::default().len(),
+            Precompiles::new(SpecId::LATEST).len()
+        );
+    }
+}
@@ -21,6 +21,18 @@ fn byzantium_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResul
     })
 }
 
+pub const CVM_MODEXP: PrecompileAddress = PrecompileAddress(
+    crate::u64_to_b176(5),
+    Precompile::Standard(cvm_modexp_run as StandardPrecompileFn),
+);
+
+/// See: https://eips.ethereum.org/EIPS/eip-2565
+fn cvm_modexp_run(input: &[u8], energy_limit: u64, _: Network) -> PrecompileResult {
+    run_inner(input, energy_limit, 200, |a, b, c, d| {
+        cvm_modexp_energy_calc(a, b, c, d)
+    })
+}
+
 fn calculate_iteration_count(exp_length: u64, exp_highp: &BigUint) -> u64 {
     let mut iteration_count: u64 = 0;
 
@@ -75,6 +87,9 @@ where
     }
 
     let (r, energy_cost) = if base_len == 0 && mod_len == 0 {
+        if min_energy > energy_limit {
+            return Err(Error::OutOfEnergy);
+        }
         (BigUint::zero(), min_energy)
     } else {
         // set limit for exp overflow
@@ -157,10 +172,25 @@ fn byzantium_energy_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &
     // mul * iter_count bounded by 2^195 < 2^256 (no overflow)
     let energy = (mul * iter_count) / U256::from(20);
 
-    if energy.as_limbs()[1] != 0 || energy.as_limbs()[2] != 0 || energy.as_limbs()[3] != 0 {
+    saturating_u64(energy)
+}
+
+fn cvm_modexp_energy_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &BigUint) -> u64 {
+    // number of 8 byte words of the longer operand, squared. Bounded by 2^122.
+    let words = U256::from(max(base_len, mod_len).div_ceil(8));
+    let mul = words * words;
+    let iter_count = U256::from(calculate_iteration_count(exp_len, exp_highp));
+    // mul * iter_count bounded by 2^186 < 2^256 (no overflow)
+    let energy = (mul * iter_count) / U256::from(3);
+
+    max(200, saturating_u64(energy))
+}
+
+fn saturating_u64(value: U256) -> u64 {
+    if value.as_limbs()[1] != 0 || value.as_limbs()[2] != 0 || value.as_limbs()[3] != 0 {
         u64::MAX
     } else {
-        energy.as_limbs()[0]
+        value.as_limbs()[0]
     }
 }
 
@@ -339,6 +369,11 @@ mod tests {
         5_580, 5_580, 89_292, 17_868, 17_868, 285_900,
     ];
 
+    const CVM_MODEXP_ENERGY: [u64; 19] = [
+        44_954, 1_360, 1_360, 1_360, 200, 200, 341, 200, 200, 1_365, 341, 341, 5_461, 1_365, 1_365,
+        21_845, 5_461, 5_461, 87_381,
+    ];
+
     #[test]
     fn test_byzantium_modexp_energy() {
         for (test, &test_energy) in TESTS.iter().zip(BYZANTIUM_ENERGY.iter()) {
@@ -354,4 +389,30 @@ mod tests {
             assert_eq!(res.1, expected, "test:{}", test.name);
         }
     }
+
+    #[test]
+    fn test_cvm_modexp_energy() {
+        for (test, &test_energy) in TESTS.iter().zip(CVM_MODEXP_ENERGY.iter()) {
+            let input = hex::decode(test.input).unwrap();
+
+            let res = cvm_modexp_run(&input, 100_000_000, Network::Mainnet).unwrap();
+            let expected = hex::decode(test.expected).unwrap();
+            assert_eq!(
+                res.0, test_energy,
+                "used energy not maching for test: {}",
+                test.name
+            );
+            assert_eq!(res.1, expected, "test:{}", test.name);
+        }
+    }
+
+    #[test]
+    fn test_cvm_modexp_empty_input() {
+        let res = cvm_modexp_run(&[], 100_000, Network::Mainnet).unwrap();
+        assert_eq!(res, (200, Vec::new()));
+        assert_eq!(
+            cvm_modexp_run(&[], 199, Network::Mainnet),
+            Err(Error::OutOfEnergy)
+        );
+    }
 }
@@ -6,8 +6,7 @@ pub const ECRECOVER: PrecompileAddress = PrecompileAddress(
 
 use crate::B256;
 use libgoldilocks::goldilocks::ed448_verify_with_error;
-use revm_primitives::{to_ican, Network, B160, B256 as rB256};
-use sha3::{Digest, Sha3_256};
+use revm_primitives::{pubkey_to_address, Network, B256 as rB256};
 
 pub fn ecrecover(
     sig: &[u8; 171],
@@ -22,11 +21,7 @@ pub fn ecrecover(
     // Not sure whether this returns address(0) on invliad message
     ed448_verify_with_error(&pub_bytes, &sig_bytes, msg.as_ref())?;
 
-    let hash = Sha3_256::digest(pub_bytes);
-    let hash: B256 = hash[..].try_into().unwrap();
-    let addr = B160::from_slice(&hash[12..]);
-    let addr = to_ican(&addr, &network);
-    let addr = rB256::from(addr);
+    let addr = rB256::from(pubkey_to_address(&pub_bytes, network));
     Ok(*addr)
 }
 
@@ -5,10 +5,12 @@ use crate::U256;
 use crate::{Account, Bytecode};
 use crate::{B176, B256};
 use auto_impl::auto_impl;
+use core::future::Future;
 use hashbrown::HashMap as Map;
 
 pub use components::{
-    BlockHash, BlockHashRef, DatabaseComponentError, DatabaseComponents, State, StateRef,
+    BlockHash, BlockHashRef, DatabaseComponentError, DatabaseComponents, RecentBlockHashes, State,
+    StateRef,
 };
 
 #[auto_impl(& mut, Box)]
@@ -46,6 +48,33 @@ pub trait DatabaseRef {
     fn block_hash(&self, number: U256) -> Result<B256, Self::Error>;
 }
 
+/// Asynchronous version of [DatabaseRef], for state that is behind network or other async IO.
+///
+/// EVM can't call it directly, `revm::db::AsyncDatabaseAdapter` fetches data from it on demand.
+/// Futures are `Send` so that the adapter can run on multi threaded executors.
+pub trait AsyncDatabase {
+    type Error;
+    /// Get basic account information.
+    fn basic(
+        &self,
+        address: B176,
+    ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send;
+    /// Get account code by its hash
+    fn code_by_hash(
+        &self,
+        code_hash: B256,
+    ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send;
+    /// Get storage value of address at index.
+    fn storage(
+        &self,
+        address: B176,
+        index: U256,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send;
+
+    // History related
+    fn block_hash(&self, number: U256) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+}
+
 pub struct RefDBWrapper<'a, Error> {
     pub db: &'a dyn DatabaseRef<Error = Error>,
 }
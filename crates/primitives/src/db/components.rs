@@ -2,7 +2,7 @@
 pub mod block_hash;
 pub mod state;
 
-pub use block_hash::{BlockHash, BlockHashRef};
+pub use block_hash::{BlockHash, BlockHashRef, RecentBlockHashes, BLOCK_HASH_HISTORY};
 pub use state::{State, StateRef};
 
 use crate::{
@@ -4,6 +4,7 @@
 use crate::{B256, U256};
 use alloc::sync::Arc;
 use auto_impl::auto_impl;
+use core::convert::Infallible;
 use core::ops::Deref;
 
 #[auto_impl(& mut, Box)]
@@ -43,3 +44,156 @@ where
         self.deref().block_hash(number)
     }
 }
+
+/// Number of most recent blocks whose hashes are available to `BLOCKHASH`.
+pub const BLOCK_HASH_HISTORY: usize = 256;
+
+/// [BlockHash] component that keeps hashes of the last [BLOCK_HASH_HISTORY] blocks
+/// in a ring buffer. Hashes of blocks outside of the window are zero.
+#[derive(Clone, Debug)]
+pub struct RecentBlockHashes {
+    hashes: [B256; BLOCK_HASH_HISTORY],
+    /// Number of the last appended block.
+    latest: U256,
+    /// Number of valid hashes, ending with the latest one.
+    len: usize,
+}
+
+impl Default for RecentBlockHashes {
+    fn default() -> Self {
+        Self {
+            hashes: [B256::zero(); BLOCK_HASH_HISTORY],
+            latest: U256::ZERO,
+            len: 0,
+        }
+    }
+}
+
+impl RecentBlockHashes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append hash of the block `number`.
+    ///
+    /// Number is expected to follow the last appended block. If it does not, hashes that are not
+    /// before it in a continuous sequence are dropped, so reorg can be applied by appending
+    /// the new blocks.
+    pub fn push(&mut self, number: U256, hash: B256) {
+        let first = self.latest - U256::from(self.len) + U256::from(1);
+        self.len = if self.len != 0 && number >= first && number <= self.latest + U256::from(1) {
+            let below = number - first;
+            // `below` is at most `BLOCK_HASH_HISTORY`.
+            (below.as_limbs()[0] as usize + 1).min(BLOCK_HASH_HISTORY)
+        } else {
+            1
+        };
+        self.latest = number;
+        self.hashes[Self::index(number)] = hash;
+    }
+
+    /// Number of the last appended block, `None` if empty.
+    pub fn latest(&self) -> Option<U256> {
+        (self.len != 0).then_some(self.latest)
+    }
+
+    fn index(number: U256) -> usize {
+        number.as_limbs()[0] as usize % BLOCK_HASH_HISTORY
+    }
+
+    fn get(&self, number: U256) -> B256 {
+        if number > self.latest || self.latest - number >= U256::from(self.len) {
+            return B256::zero();
+        }
+        self.hashes[Self::index(number)]
+    }
+}
+
+impl BlockHashRef for RecentBlockHashes {
+    type Error = Infallible;
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        Ok(self.get(number))
+    }
+}
+
+impl BlockHash for RecentBlockHashes {
+    type Error = Infallible;
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        Ok(self.get(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecentBlockHashes, BLOCK_HASH_HISTORY};
+    use crate::db::components::{DatabaseComponents, StateRef};
+    use crate::db::DatabaseRef;
+    use crate::{AccountInfo, Bytecode, B176, B256, U256};
+    use core::convert::Infallible;
+
+    fn hash(number: u64) -> B256 {
+        B256(U256::from(number + 1).to_be_bytes())
+    }
+
+    fn filled(to: u64) -> RecentBlockHashes {
+        let mut hashes = RecentBlockHashes::new();
+        for number in 0..=to {
+            hashes.push(U256::from(number), hash(number));
+        }
+        hashes
+    }
+
+    #[test]
+    fn test_window() {
+        let hashes = filled(300);
+        let get = |number: u64| super::BlockHashRef::block_hash(&hashes, U256::from(number));
+        assert_eq!(hashes.latest(), Some(U256::from(300)));
+        assert_eq!(get(300), Ok(hash(300)));
+        assert_eq!(get(300 - BLOCK_HASH_HISTORY as u64 + 1), Ok(hash(45)));
+        assert_eq!(get(300 - BLOCK_HASH_HISTORY as u64), Ok(B256::zero()));
+        assert_eq!(get(301), Ok(B256::zero()));
+        assert_eq!(RecentBlockHashes::new().latest(), None);
+    }
+
+    #[test]
+    fn test_reorg_and_gap() {
+        let mut hashes = filled(10);
+        hashes.push(U256::from(8), B256::repeat_byte(8));
+        assert_eq!(hashes.get(U256::from(7)), hash(7));
+        assert_eq!(hashes.get(U256::from(8)), B256::repeat_byte(8));
+        assert_eq!(hashes.get(U256::from(9)), B256::zero());
+
+        hashes.push(U256::from(20), hash(20));
+        assert_eq!(hashes.get(U256::from(7)), B256::zero());
+        assert_eq!(hashes.get(U256::from(20)), hash(20));
+    }
+
+    struct EmptyState;
+
+    impl StateRef for EmptyState {
+        type Error = Infallible;
+
+        fn basic(&self, _address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(None)
+        }
+
+        fn code_by_hash(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::new())
+        }
+
+        fn storage(&self, _address: B176, _index: U256) -> Result<U256, Self::Error> {
+            Ok(U256::ZERO)
+        }
+    }
+
+    #[test]
+    fn test_database_components() {
+        let db = DatabaseComponents {
+            state: EmptyState,
+            block_hash: filled(5),
+        };
+        assert!(matches!(db.block_hash(U256::from(5)), Ok(h) if h == hash(5)));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::Network;
 use alloc::vec::Vec;
+use core::fmt;
 use libgoldilocks::errors::LibgoldilockErrors;
 
 /// A precompile operation result.
@@ -8,7 +9,8 @@ pub type PrecompileResult = Result<(u64, Vec<u8>), PrecompileError>;
 pub type StandardPrecompileFn = fn(&[u8], u64, Network) -> PrecompileResult;
 pub type CustomPrecompileFn = fn(&[u8], u64, Network) -> PrecompileResult;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrecompileError {
     /// out of energy is the main error. Other are just here for completness
     OutOfEnergy,
@@ -32,8 +34,74 @@ pub enum PrecompileError {
     EcrecoverInvalidPubkeyLengthError,
     EcrecoverInvalidSignatureLengthError,
     EcrecoverInvalidSignatureError,
+    // Ed448 errors
+    Ed448VerifyInputLength,
+    Ed448PublicKeyLength,
+    // BLS12-381 errors
+    Bls12381InputLength,
+    Bls12381FpPaddingInvalid,
+    Bls12381FpNotInField,
+    Bls12381PointNotOnCurve,
+    Bls12381PointNotInSubgroup,
+    /// Precompile failure without a recorded error, e.g. result set by an inspector.
+    Other,
 }
 
+impl fmt::Display for PrecompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfEnergy => f.write_str("out of energy"),
+            Self::Blake2WrongLength => f.write_str("wrong input length for blake2"),
+            Self::Blake2WrongFinalIndicatorFlag => {
+                f.write_str("wrong final indicator flag for blake2")
+            }
+            Self::ModexpExpOverflow => f.write_str("modexp exponent length overflow"),
+            Self::ModexpBaseOverflow => f.write_str("modexp base length overflow"),
+            Self::ModexpModOverflow => f.write_str("modexp modulus length overflow"),
+            Self::Bn128FieldPointNotAMember => {
+                f.write_str("field point is not a member of bn128 curve")
+            }
+            Self::Bn128AffineGFailedToCreate => f.write_str("failed to create bn128 affine point"),
+            Self::Bn128PairLength => f.write_str("bn128 pairing input has invalid length"),
+            Self::EcrecoverDecodeError => f.write_str("ecrecover failed to decode input"),
+            Self::EcrecoverDecodePubkeyError => {
+                f.write_str("ecrecover failed to decode public key")
+            }
+            Self::EcrecoverDecodeSignatureError => {
+                f.write_str("ecrecover failed to decode signature")
+            }
+            Self::EcrecoverInvalidLengthError => f.write_str("ecrecover input has invalid length"),
+            Self::EcrecoverInvalidPrivKeyLengthError(len) => {
+                write!(f, "ecrecover private key has invalid length {len}")
+            }
+            Self::EcrecoverInvalidPubkeyLengthError => {
+                f.write_str("ecrecover public key has invalid length")
+            }
+            Self::EcrecoverInvalidSignatureLengthError => {
+                f.write_str("ecrecover signature has invalid length")
+            }
+            Self::EcrecoverInvalidSignatureError => f.write_str("ecrecover signature is invalid"),
+            Self::Ed448VerifyInputLength => {
+                f.write_str("ed448 verify input is shorter than public key and signature")
+            }
+            Self::Ed448PublicKeyLength => f.write_str("ed448 public key has invalid length"),
+            Self::Bls12381InputLength => f.write_str("bls12-381 input has invalid length"),
+            Self::Bls12381FpPaddingInvalid => {
+                f.write_str("bls12-381 field element padding is not zero")
+            }
+            Self::Bls12381FpNotInField => f.write_str("bls12-381 field element is not in field"),
+            Self::Bls12381PointNotOnCurve => f.write_str("bls12-381 point is not on curve"),
+            Self::Bls12381PointNotInSubgroup => {
+                f.write_str("bls12-381 point is not in the subgroup")
+            }
+            Self::Other => f.write_str("precompile failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrecompileError {}
+
 impl From<LibgoldilockErrors> for PrecompileError {
     fn from(value: LibgoldilockErrors) -> Self {
         match value {
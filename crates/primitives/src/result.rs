@@ -1,6 +1,7 @@
-use crate::{Log, State, B176};
+use crate::{Log, PrecompileError, State, B176};
 use alloc::vec::Vec;
 use bytes::Bytes;
+use core::fmt;
 use ruint::aliases::U256;
 
 pub type EVMResult<DB> = core::result::Result<ResultAndState, EVMError<DB>>;
@@ -96,6 +97,26 @@ impl<DB> From<InvalidTransaction> for EVMError<DB> {
     }
 }
 
+impl<DB: fmt::Display> fmt::Display for EVMError<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transaction(invalid) => write!(f, "invalid transaction: {invalid}"),
+            Self::PrevrandaoNotSet => f.write_str("prevrandao is not set"),
+            Self::Database(error) => write!(f, "database error: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<DB: fmt::Debug + fmt::Display> std::error::Error for EVMError<DB> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transaction(invalid) => Some(invalid),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InvalidTransaction {
@@ -127,6 +148,44 @@ pub enum InvalidTransaction {
     InvalidNetworkId,
 }
 
+impl fmt::Display for InvalidTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EnergyMaxFeeGreaterThanPriorityFee => {
+                f.write_str("max priority fee is greater than max fee")
+            }
+            Self::EnergyPriceLessThanBasefee => f.write_str("energy price is less than basefee"),
+            Self::CallerEnergyLimitMoreThanBlock => {
+                f.write_str("energy limit is greater than block energy limit")
+            }
+            Self::CallEnergyCostMoreThanEnergyLimit => {
+                f.write_str("intrinsic energy cost is greater than energy limit")
+            }
+            Self::RejectCallerWithCode => f.write_str("caller has deployed code"),
+            Self::LackOfFundForEnergyLimit {
+                energy_limit,
+                balance,
+            } => write!(
+                f,
+                "lack of funds, balance {balance} is less than required {energy_limit}"
+            ),
+            Self::OverflowPaymentInTransaction => f.write_str("overflow payment in transaction"),
+            Self::NonceOverflowInTransaction => f.write_str("nonce overflow in transaction"),
+            Self::NonceTooHigh { tx, state } => {
+                write!(f, "nonce {tx} is higher than account nonce {state}")
+            }
+            Self::NonceTooLow { tx, state } => {
+                write!(f, "nonce {tx} is lower than account nonce {state}")
+            }
+            Self::CreateInitcodeSizeLimit => f.write_str("initcode size limit exceeded"),
+            Self::InvalidNetworkId => f.write_str("invalid network id"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidTransaction {}
+
 /// When transaction return successfully without halts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -150,7 +209,7 @@ pub enum Halt {
     StackOverflow,
     OutOfOffset,
     CreateCollision,
-    PrecompileError,
+    PrecompileError(PrecompileError),
     NonceOverflow,
     /// Create init code size exceeds limit (runtime).
     CreateContractSizeLimit,
@@ -158,6 +217,8 @@ pub enum Halt {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitcodeSizeLimit,
+    /// Execution limit set by inspector was exceeded.
+    ExecutionLimit(ExecutionLimitError),
 
     /* Internal Halts that can be only found inside Inspector */
     OverflowPayment,
@@ -167,6 +228,46 @@ pub enum Halt {
     CallTooDeep,
 }
 
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfEnergy(error) => match error {
+                OutOfEnergyError::BasicOutOfEnergy => f.write_str("out of energy"),
+                OutOfEnergyError::MemoryLimit => f.write_str("out of energy: memory limit"),
+                OutOfEnergyError::Memory => f.write_str("out of energy: memory expansion"),
+                OutOfEnergyError::Precompile => f.write_str("out of energy: precompile"),
+                OutOfEnergyError::InvalidOperand => f.write_str("out of energy: invalid operand"),
+            },
+            Self::OpcodeNotFound => f.write_str("opcode not found"),
+            Self::InvalidFEOpcode => f.write_str("invalid 0xFE opcode"),
+            Self::InvalidJump => f.write_str("invalid jump destination"),
+            Self::NotActivated => f.write_str("feature or opcode not activated"),
+            Self::StackUnderflow => f.write_str("stack underflow"),
+            Self::StackOverflow => f.write_str("stack overflow"),
+            Self::OutOfOffset => f.write_str("out of offset"),
+            Self::CreateCollision => f.write_str("create collision"),
+            Self::PrecompileError(error) => write!(f, "precompile error: {error}"),
+            Self::NonceOverflow => f.write_str("nonce overflow"),
+            Self::CreateContractSizeLimit => f.write_str("create contract size limit exceeded"),
+            Self::CreateContractStartingWithEF => f.write_str("created contract starts with 0xEF"),
+            Self::CreateInitcodeSizeLimit => f.write_str("initcode size limit exceeded"),
+            Self::ExecutionLimit(error) => match error {
+                ExecutionLimitError::Steps => f.write_str("execution limit: steps"),
+                ExecutionLimitError::Time => f.write_str("execution limit: time"),
+                ExecutionLimitError::Memory => f.write_str("execution limit: memory"),
+            },
+            Self::OverflowPayment => f.write_str("overflow payment"),
+            Self::StateChangeDuringStaticCall => f.write_str("state change during static call"),
+            Self::CallNotAllowedInsideStatic => f.write_str("call not allowed inside static call"),
+            Self::OutOfFund => f.write_str("out of funds"),
+            Self::CallTooDeep => f.write_str("call too deep"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Halt {}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutOfEnergyError {
@@ -182,3 +283,14 @@ pub enum OutOfEnergyError {
     // i.e. in `as_usize_or_fail`
     InvalidOperand,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExecutionLimitError {
+    // Number of executed instructions exceeded the limit
+    Steps,
+    // Wall-clock execution time exceeded the limit
+    Time,
+    // Total memory of all active call frames exceeded the limit
+    Memory,
+}
@@ -15,7 +15,10 @@ pub enum SpecId {
     CONSTANTINOPLE = 7,   // Constantinople         7280000 is overwritten with PETERSBURG
     PETERSBURG = 8,       // Petersburg             7280000
     ISTANBUL = 9,         // Istanbul	            9069000
-    LATEST = 10,
+    CVM_ED448 = 10,       // CVM fork with Ed448 and SHA3-512 precompiles
+    CVM_MODEXP = 11,      // CVM fork with EIP-2565 modexp repricing
+    CVM_BLS = 12,         // CVM fork with EIP-2537 BLS12-381 precompiles
+    LATEST = 13,
 }
 
 impl SpecId {
@@ -37,6 +40,9 @@ impl From<&str> for SpecId {
             "Constantinople" => SpecId::CONSTANTINOPLE,
             "Petersburg" => SpecId::PETERSBURG,
             "Istanbul" => SpecId::ISTANBUL,
+            "CvmEd448" => SpecId::CVM_ED448,
+            "CvmModexp" => SpecId::CVM_MODEXP,
+            "CvmBls" => SpecId::CVM_BLS,
             _ => SpecId::LATEST,
         }
     }
@@ -78,4 +84,7 @@ spec!(BYZANTIUM, ByzantiumSpec);
 // CONSTANTINOPLE was overriden with PETERSBURG
 spec!(PETERSBURG, PetersburgSpec);
 spec!(ISTANBUL, IstanbulSpec);
+spec!(CVM_ED448, CvmEd448Spec);
+spec!(CVM_MODEXP, CvmModexpSpec);
+spec!(CVM_BLS, CvmBlsSpec);
 spec!(LATEST, LatestSpec);
@@ -82,6 +82,17 @@ pub fn create2_address(caller: B176, code_hash: B256, salt: U256, network: Netwo
     to_ican(&addr, &network)
 }
 
+/// Returns the address of the account controlled by the Ed448 public key.
+pub fn pubkey_to_address(pubkey: &[u8], network: Network) -> B176 {
+    let hash = sha3(pubkey);
+
+    // Get the last 20 bytes of the hash
+    let addr = B160::from_slice(&hash[12..]);
+
+    // Calculate the checksum and add the network prefix
+    to_ican(&addr, &network)
+}
+
 pub fn to_ican(addr: &B160, network: &Network) -> B176 {
     // Get the prefix str
     let prefix = match network {
@@ -177,6 +188,21 @@ pub mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn test_pubkey_to_address() {
+        // public key from the signature of the secp256k1 precompile test.
+        let pubkey = hex!("3761457fcdd53dba3dea5848c43aa54fe468284319f032945a3acb9bd4cd0fa7b7c901d978e9acd9eca43fa5b3c32b648c33dcc3f3169e8080");
+        let mainnet = pubkey_to_address(&pubkey, Network::Mainnet);
+        let devin = pubkey_to_address(&pubkey, Network::Devin);
+
+        assert_eq!(
+            mainnet,
+            B176::from_str("cb58fc37a3b370a1f22e2fe2f819c210895e098845ed").unwrap()
+        );
+        assert_eq!(devin[0], 0xab);
+        assert_eq!(mainnet[2..], devin[2..]);
+    }
+
     #[test]
     fn test_create_one() {
         let caller = B176::from_str("cb72e8cF4629ACB360350399B6CFF367A97CF36E62B9").unwrap();
@@ -0,0 +1,198 @@
+//! Analysed bytecode cache shared between transactions.
+//!
+//! Code loaded from database is usually raw and analysis (jump table creation) is done each time
+//! a contract is entered. [BytecodeCache] keeps analysed bytecode keyed by code hash so it is done
+//! only once per contract. Cache is thread-safe and can be shared between EVMs with [Arc].
+//!
+//! [Arc]: std::sync::Arc
+use crate::interpreter::analysis::to_analysed;
+use crate::lru::LruIndex;
+use crate::primitives::{Bytecode, BytecodeState, HashMap, B256, SHA3_EMPTY};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+};
+
+/// Hit/miss statistics and current size of the cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BytecodeCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of cached bytecodes.
+    pub entries: usize,
+    /// Sum of cached bytecode lengths in bytes.
+    pub size: usize,
+}
+
+/// Size bounded cache of analysed bytecode. When full, least recently used bytecode is evicted.
+#[derive(Debug)]
+pub struct BytecodeCache {
+    inner: Mutex<LruBytecodes>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug)]
+struct LruBytecodes {
+    entries: HashMap<B256, Bytecode>,
+    /// Bytecode lengths in recency order, capacity is maximum sum of lengths.
+    lru: LruIndex<B256>,
+}
+
+impl BytecodeCache {
+    /// Create cache that holds up to `capacity` bytes of bytecode.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(LruBytecodes::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Return analysed bytecode for the hash, if cached.
+    pub fn get(&self, hash: &B256) -> Option<Bytecode> {
+        let found = self.inner.lock().unwrap().get(hash);
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    /// Analyse and insert bytecode, returning the analysed version.
+    pub fn insert(&self, bytecode: Bytecode) -> Bytecode {
+        let bytecode = to_analysed(bytecode);
+        if bytecode.hash != SHA3_EMPTY {
+            self.inner.lock().unwrap().insert(bytecode.clone());
+        }
+        bytecode
+    }
+
+    /// Return analysed bytecode, from cache if present or by analysing and caching it.
+    ///
+    /// Bytecode that is already analysed or empty is returned as is and is not counted in stats.
+    pub fn get_or_analyse(&self, bytecode: Bytecode) -> Bytecode {
+        if matches!(bytecode.state, BytecodeState::Analysed { .. }) || bytecode.hash == SHA3_EMPTY {
+            return bytecode;
+        }
+        match self.get(&bytecode.hash) {
+            Some(cached) => cached,
+            None => self.insert(bytecode),
+        }
+    }
+
+    pub fn stats(&self) -> BytecodeCacheStats {
+        let inner = self.inner.lock().unwrap();
+        BytecodeCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: inner.lru.len(),
+            size: inner.lru.size(),
+        }
+    }
+
+    /// Remove all entries and reset statistics.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner = LruBytecodes::new(inner.lru.capacity());
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+impl LruBytecodes {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            lru: LruIndex::new(capacity),
+        }
+    }
+
+    fn get(&mut self, hash: &B256) -> Option<Bytecode> {
+        let bytecode = self.entries.get(hash)?.clone();
+        self.lru.touch(hash);
+        Some(bytecode)
+    }
+
+    fn insert(&mut self, bytecode: Bytecode) {
+        if self.entries.contains_key(&bytecode.hash) {
+            return;
+        }
+        let entries = &mut self.entries;
+        if self.lru.insert(bytecode.hash, bytecode.len(), |hash| {
+            entries.remove(&hash);
+        }) {
+            entries.insert(bytecode.hash, bytecode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytecodeCache;
+    use crate::db::BenchmarkDB;
+    use crate::primitives::{hex_literal::hex, Bytecode, BytecodeState, Bytes, TransactTo, B176};
+    use std::sync::Arc;
+
+    fn bytecode(code: &[u8]) -> Bytecode {
+        Bytecode::new_raw(Bytes::copy_from_slice(code))
+    }
+
+    #[test]
+    fn test_get_or_analyse() {
+        let cache = BytecodeCache::new(1024);
+        let code = bytecode(&[0x60, 0x01, 0x5b, 0x00]);
+
+        let analysed = cache.get_or_analyse(code.clone());
+        assert!(matches!(analysed.state, BytecodeState::Analysed { .. }));
+        assert_eq!(cache.get_or_analyse(code.clone()), analysed);
+        // already analysed bytecode is not looked up.
+        cache.get_or_analyse(analysed);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!((stats.entries, stats.size), (1, 4));
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let cache = BytecodeCache::new(8);
+        let (first, second, third) = (
+            bytecode(&[0x01; 4]),
+            bytecode(&[0x02; 4]),
+            bytecode(&[0x03; 4]),
+        );
+        cache.insert(first.clone());
+        cache.insert(second.clone());
+        // touch first so second is evicted.
+        assert!(cache.get(&first.hash).is_some());
+        cache.insert(third.clone());
+
+        assert!(cache.get(&first.hash).is_some());
+        assert!(cache.get(&second.hash).is_none());
+        assert!(cache.get(&third.hash).is_some());
+        assert_eq!(cache.stats().size, 8);
+    }
+
+    #[test]
+    fn test_shared_between_transactions() {
+        let cache = Arc::new(BytecodeCache::new(1024));
+        let code = bytecode(&[0x60, 0x01, 0x60, 0x01, 0x01, 0x00]);
+
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(code));
+        evm.bytecode_cache(cache.clone());
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+        evm.env.tx.energy_limit = 100_000;
+
+        evm.transact().unwrap();
+        evm.clone().transact().unwrap();
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    }
+}
@@ -1,4 +1,18 @@
+pub mod async_db;
 pub mod in_memory_db;
+pub mod lru_cache_db;
+pub mod metrics_db;
+pub mod override_db;
+#[cfg(feature = "persistent_db")]
+pub mod persistent_db;
+pub mod shared_cache_db;
 
 pub use crate::primitives::db::*;
+pub use async_db::{AsyncAdapterError, AsyncDatabaseAdapter, NotFetched};
 pub use in_memory_db::*;
+pub use lru_cache_db::LruCacheDB;
+pub use metrics_db::{AccessLogger, AccessMetrics, MetricsDB, MetricsReport};
+pub use override_db::{AccountOverride, BlockOverrides, OverrideDB, OverrideError};
+#[cfg(feature = "persistent_db")]
+pub use persistent_db::{PersistentDB, PersistentDBError};
+pub use shared_cache_db::SharedCacheDB;
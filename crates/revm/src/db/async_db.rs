@@ -0,0 +1,399 @@
+//! Running EVM against [AsyncDatabase].
+//!
+//! [AsyncDatabaseAdapter] keeps data fetched so far in a [CacheDB] and executes transactions
+//! with [crate::resumable] execution. When data is not cached, execution is suspended, data is
+//! awaited from async database and execution is resumed, so no thread is blocked on IO and there is
+//! no need to declare accessed state upfront.
+use super::{CacheDB, DatabaseCommit, DatabaseRef, DbAccount};
+use crate::primitives::{
+    db::AsyncDatabase, AccountInfo, Bytecode, EVMError, EVMResult, Env, ExecutionResult,
+    ResultAndState, B176, B256, U256,
+};
+use crate::resumable::{DataRequest, ResumeResult};
+use crate::{evm_inner_resumable_send, inspectors::NoOpInspector, Inspector};
+
+/// Database without any data, placed under [CacheDB] of [AsyncDatabaseAdapter]
+/// so that everything not yet fetched is reported as missing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NotFetched;
+
+impl DatabaseRef for NotFetched {
+    type Error = NotFetched;
+
+    fn basic(&self, _address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        Err(NotFetched)
+    }
+
+    fn code_by_hash(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Err(NotFetched)
+    }
+
+    fn storage(&self, _address: B176, _index: U256) -> Result<U256, Self::Error> {
+        Err(NotFetched)
+    }
+
+    fn block_hash(&self, _number: U256) -> Result<B256, Self::Error> {
+        Err(NotFetched)
+    }
+}
+
+/// Error of [AsyncDatabaseAdapter].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsyncAdapterError<E> {
+    /// Async database failed to provide data.
+    Database(E),
+    /// State that was not fetched was accessed outside of the execution, e.g. by an inspector.
+    /// Execution only fetches state before it accesses it.
+    NotFetched,
+}
+
+/// Executes transactions against [AsyncDatabase], fetching state on demand.
+///
+/// Fetched state is cached and changes committed with [AsyncDatabaseAdapter::transact_commit]
+/// are kept in the cache on top of it, async database is only read.
+///
+/// Returned futures are `Send` when the async database is `Sync` and its error is `Send`.
+#[derive(Debug)]
+pub struct AsyncDatabaseAdapter<ADB> {
+    cache: CacheDB<NotFetched>,
+    db: ADB,
+}
+
+impl<ADB: AsyncDatabase> AsyncDatabaseAdapter<ADB> {
+    pub fn new(db: ADB) -> Self {
+        Self {
+            cache: CacheDB::new(NotFetched),
+            db,
+        }
+    }
+
+    /// State fetched so far, with committed changes.
+    pub fn cache(&self) -> &CacheDB<NotFetched> {
+        &self.cache
+    }
+
+    pub fn db(&self) -> &ADB {
+        &self.db
+    }
+
+    /// Execute transaction without committing it, return changed state.
+    pub async fn transact(&mut self, env: &Env) -> EVMResult<AsyncAdapterError<ADB::Error>> {
+        self.inspect(env, NoOpInspector {}).await
+    }
+
+    /// Execute transaction with given inspector without committing it, return changed state.
+    ///
+    /// Inspector can read only state that execution has already fetched, reading anything else
+    /// fails the transaction with [AsyncAdapterError::NotFetched].
+    pub async fn inspect<INSP: Inspector<CacheDB<NotFetched>> + Send>(
+        &mut self,
+        env: &Env,
+        mut inspector: INSP,
+    ) -> EVMResult<AsyncAdapterError<ADB::Error>> {
+        let mut env = env.clone();
+        let mut execution =
+            evm_inner_resumable_send(&mut env, &mut self.cache, &mut inspector, None);
+        loop {
+            match execution.resume() {
+                Ok(ResumeResult::Done(result)) => return Ok(result),
+                Ok(ResumeResult::NeedsData { request, .. }) => {
+                    fetch(&self.db, execution.db(), request)
+                        .await
+                        .map_err(|e| EVMError::Database(AsyncAdapterError::Database(e)))?;
+                }
+                Err(EVMError::Transaction(invalid)) => return Err(EVMError::Transaction(invalid)),
+                Err(EVMError::PrevrandaoNotSet) => return Err(EVMError::PrevrandaoNotSet),
+                Err(EVMError::Database(NotFetched)) => {
+                    return Err(EVMError::Database(AsyncAdapterError::NotFetched))
+                }
+            }
+        }
+    }
+
+    /// Execute transaction and commit changes to the cache.
+    pub async fn transact_commit(
+        &mut self,
+        env: &Env,
+    ) -> Result<ExecutionResult, EVMError<AsyncAdapterError<ADB::Error>>> {
+        let ResultAndState { result, state } = self.transact(env).await?;
+        self.cache.commit(state);
+        Ok(result)
+    }
+}
+
+/// Fetch requested data from async database and insert it into the cache.
+async fn fetch<ADB: AsyncDatabase>(
+    db: &ADB,
+    cache: &mut CacheDB<NotFetched>,
+    request: DataRequest,
+) -> Result<(), ADB::Error> {
+    match request {
+        DataRequest::Account(address) => fetch_account(db, cache, address).await?,
+        DataRequest::Storage(address, index) => {
+            if !cache.accounts.contains_key(&address) {
+                fetch_account(db, cache, address).await?;
+            }
+            let value = db.storage(address, index).await?;
+            // account is cached above so inserting can't fail.
+            let _ = cache.insert_account_storage(address, index, value);
+        }
+        DataRequest::Code(code_hash) => {
+            let code = db.code_by_hash(code_hash).await?;
+            cache.contracts.insert(code_hash, code);
+        }
+        DataRequest::BlockHash(number) => {
+            let hash = db.block_hash(number).await?;
+            cache.block_hashes.insert(number, hash);
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_account<ADB: AsyncDatabase>(
+    db: &ADB,
+    cache: &mut CacheDB<NotFetched>,
+    address: B176,
+) -> Result<(), ADB::Error> {
+    match db.basic(address).await? {
+        Some(info) => cache.insert_account_info(address, info),
+        None => {
+            cache
+                .accounts
+                .insert(address, DbAccount::new_not_existing());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncAdapterError, AsyncDatabaseAdapter, NotFetched};
+    use crate::db::{CacheDB, Database, DatabaseRef, InMemoryDB};
+    use crate::interpreter::{opcode, CallInputs, Energy, InstructionResult};
+    use crate::primitives::{
+        db::AsyncDatabase, hex_literal::hex, AccountInfo, Bytecode, Bytes, EVMError, Env,
+        TransactTo, B176, B256, U256,
+    };
+    use crate::{EVMData, Inspector};
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    /// Async backend over in memory state, every request yields to the executor once.
+    struct LocalBackend {
+        state: InMemoryDB,
+        requests: AtomicUsize,
+    }
+
+    impl LocalBackend {
+        async fn answer<T>(&self, value: T) -> Result<T, ()> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            YieldOnce(false).await;
+            Ok(value)
+        }
+    }
+
+    impl AsyncDatabase for LocalBackend {
+        type Error = ();
+
+        fn basic(
+            &self,
+            address: B176,
+        ) -> impl Future<Output = Result<Option<AccountInfo>, Self::Error>> + Send {
+            // code is fetched separately.
+            let info = self.state.basic(address).unwrap().map(|mut info| {
+                info.code = None;
+                info
+            });
+            self.answer(info)
+        }
+
+        fn code_by_hash(
+            &self,
+            code_hash: B256,
+        ) -> impl Future<Output = Result<Bytecode, Self::Error>> + Send {
+            self.answer(self.state.code_by_hash(code_hash).unwrap())
+        }
+
+        fn storage(
+            &self,
+            address: B176,
+            index: U256,
+        ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
+            self.answer(self.state.storage(address, index).unwrap())
+        }
+
+        fn block_hash(
+            &self,
+            number: U256,
+        ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+            self.answer(self.state.block_hash(number).unwrap())
+        }
+    }
+
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    fn setup() -> (InMemoryDB, Env) {
+        let caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        // increment slot 0
+        let code = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x1,
+            opcode::ADD,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+
+        let mut state = InMemoryDB::default();
+        state.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+        state.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, Bytecode::new_raw(code)),
+        );
+        state
+            .insert_account_storage(contract, U256::ZERO, U256::from(41))
+            .unwrap();
+
+        let mut env = Env::default();
+        env.tx.caller = caller;
+        env.tx.transact_to = TransactTo::Call(contract);
+        env.tx.energy_limit = 100_000;
+        (state, env)
+    }
+
+    #[test]
+    fn test_same_result_as_sync() {
+        let (state, env) = setup();
+        let mut evm = crate::EVM::with_env(env.clone());
+        evm.database(state.clone());
+        let expected = evm.transact().unwrap();
+
+        let mut adapter = AsyncDatabaseAdapter::new(LocalBackend {
+            state,
+            requests: AtomicUsize::new(0),
+        });
+        let result = block_on(adapter.transact(&env)).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_commit_to_cache() {
+        let (state, mut env) = setup();
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        let mut adapter = AsyncDatabaseAdapter::new(LocalBackend {
+            state,
+            requests: AtomicUsize::new(0),
+        });
+
+        assert!(block_on(adapter.transact_commit(&env))
+            .unwrap()
+            .is_success());
+        let requests = adapter.db().requests.load(Ordering::Relaxed);
+        env.tx.nonce = Some(1);
+        assert!(block_on(adapter.transact_commit(&env))
+            .unwrap()
+            .is_success());
+
+        // everything was cached by the first transaction.
+        assert_eq!(adapter.db().requests.load(Ordering::Relaxed), requests);
+        assert_eq!(
+            adapter.cache().storage(contract, U256::ZERO),
+            Ok(U256::from(43))
+        );
+    }
+
+    #[test]
+    fn test_transact_on_other_thread() {
+        let (state, env) = setup();
+        let mut adapter = AsyncDatabaseAdapter::new(LocalBackend {
+            state,
+            requests: AtomicUsize::new(0),
+        });
+        // future is created here and polled on a different thread.
+        let result = thread::scope(|scope| {
+            let future = adapter.transact(&env);
+            scope.spawn(move || block_on(future)).join().unwrap()
+        });
+        assert!(result.unwrap().result.is_success());
+    }
+
+    /// Reads account that execution did not access.
+    struct ReadUnrelatedAccount;
+
+    impl Inspector<CacheDB<NotFetched>> for ReadUnrelatedAccount {
+        fn call(
+            &mut self,
+            data: &mut EVMData<'_, CacheDB<NotFetched>>,
+            _inputs: &mut CallInputs,
+            _is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            let unrelated = B176(hex!("20000000000000000000000000000000000000000000"));
+            if let Err(error) = data.db.basic(unrelated) {
+                data.error = Some(error);
+                return (
+                    InstructionResult::FatalExternalError,
+                    Energy::new(0),
+                    Bytes::new(),
+                );
+            }
+            (InstructionResult::Continue, Energy::new(0), Bytes::new())
+        }
+    }
+
+    #[test]
+    fn test_inspector_reading_not_fetched_state() {
+        let (state, env) = setup();
+        let mut adapter = AsyncDatabaseAdapter::new(LocalBackend {
+            state,
+            requests: AtomicUsize::new(0),
+        });
+        let result = block_on(adapter.inspect(&env, ReadUnrelatedAccount));
+        assert_eq!(
+            result,
+            Err(EVMError::Database(AsyncAdapterError::NotFetched))
+        );
+    }
+}
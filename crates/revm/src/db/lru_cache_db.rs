@@ -0,0 +1,343 @@
+//! Cache database with bounded memory usage.
+//!
+//! [LruCacheDB] caches accounts, storage slots and contracts loaded from the underlying database
+//! like [crate::db::CacheDB], but when approximate size of cached data exceeds the capacity, least
+//! recently used entries are evicted. Only clean entries, same as in the underlying database, are
+//! evicted and loaded again when needed. Entries changed by [DatabaseCommit::commit] are not
+//! counted in the size and are kept until the cache is dropped.
+use super::{DatabaseCommit, DatabaseRef};
+use crate::lru::LruIndex;
+use crate::primitives::{Account, AccountInfo, Bytecode, HashMap, B176, B256, U256};
+use crate::Database;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+const ACCOUNT_SIZE: usize = size_of::<B176>() + size_of::<CachedAccount>();
+const SLOT_SIZE: usize = size_of::<(B176, U256)>() + size_of::<U256>();
+const CONTRACT_SIZE: usize = size_of::<B256>() + size_of::<Bytecode>();
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CachedAccount {
+    /// `None` if account does not exist. `code` is always `None`, code is in contracts.
+    info: Option<AccountInfo>,
+    /// If set, storage slots that are not cached are zero.
+    storage_cleared: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Account(B176),
+    Storage(B176, U256),
+    Contract(B256),
+}
+
+/// Cache over [DatabaseRef] that evicts least recently used clean entries when it is full.
+#[derive(Clone, Debug)]
+pub struct LruCacheDB<ExtDB: DatabaseRef> {
+    accounts: HashMap<B176, CachedAccount>,
+    storage: HashMap<(B176, U256), U256>,
+    contracts: HashMap<B256, Bytecode>,
+    /// Approximate sizes of clean entries in recency order, entries that are not in it are
+    /// changed and can't be evicted.
+    lru: LruIndex<CacheKey>,
+    pub db: ExtDB,
+}
+
+impl<ExtDB: DatabaseRef> LruCacheDB<ExtDB> {
+    /// Create cache that holds up to `capacity` bytes of clean entries.
+    pub fn new(db: ExtDB, capacity: usize) -> Self {
+        Self {
+            accounts: HashMap::new(),
+            storage: HashMap::new(),
+            contracts: HashMap::new(),
+            lru: LruIndex::new(capacity),
+            db,
+        }
+    }
+
+    /// Approximate size in bytes of clean entries.
+    pub fn size(&self) -> usize {
+        self.lru.size()
+    }
+
+    /// Number of changed accounts, storage slots and contracts.
+    pub fn dirty_entries(&self) -> usize {
+        self.accounts.len() + self.storage.len() + self.contracts.len() - self.lru.len()
+    }
+
+    /// Add clean entry of `size` bytes, evicting least recently used ones to make room for it.
+    /// Returns false if entry does not fit in the cache.
+    fn insert_clean(&mut self, key: CacheKey, size: usize) -> bool {
+        let (accounts, storage, contracts) =
+            (&mut self.accounts, &mut self.storage, &mut self.contracts);
+        self.lru.insert(key, size, |evicted| match evicted {
+            CacheKey::Account(address) => {
+                accounts.remove(&address);
+            }
+            CacheKey::Storage(address, index) => {
+                storage.remove(&(address, index));
+            }
+            CacheKey::Contract(hash) => {
+                contracts.remove(&hash);
+            }
+        })
+    }
+
+    fn insert_clean_account(&mut self, address: B176, info: Option<AccountInfo>) {
+        if self.insert_clean(CacheKey::Account(address), ACCOUNT_SIZE) {
+            let account = CachedAccount {
+                info,
+                storage_cleared: false,
+            };
+            self.accounts.insert(address, account);
+        }
+    }
+
+    fn insert_clean_slot(&mut self, address: B176, index: U256, value: U256) {
+        if self.insert_clean(CacheKey::Storage(address, index), SLOT_SIZE) {
+            self.storage.insert((address, index), value);
+        }
+    }
+
+    fn insert_clean_contract(&mut self, code: Bytecode) {
+        if self.insert_clean(CacheKey::Contract(code.hash), CONTRACT_SIZE + code.len()) {
+            self.contracts.insert(code.hash, code);
+        }
+    }
+
+    /// Replace account with a changed one that is never evicted.
+    fn insert_dirty_account(&mut self, address: B176, value: CachedAccount) {
+        self.lru.remove(&CacheKey::Account(address));
+        self.accounts.insert(address, value);
+    }
+
+    fn insert_dirty_slot(&mut self, address: B176, index: U256, value: U256) {
+        self.lru.remove(&CacheKey::Storage(address, index));
+        self.storage.insert((address, index), value);
+    }
+
+    /// Remove all cached storage slots of the account.
+    fn clear_storage(&mut self, address: B176) {
+        let slots: Vec<U256> = self
+            .storage
+            .keys()
+            .filter(|(slot_address, _)| *slot_address == address)
+            .map(|(_, index)| *index)
+            .collect();
+        for index in slots {
+            self.lru.remove(&CacheKey::Storage(address, index));
+            self.storage.remove(&(address, index));
+        }
+    }
+}
+
+impl<ExtDB: DatabaseRef> DatabaseCommit for LruCacheDB<ExtDB> {
+    fn commit(&mut self, changes: HashMap<B176, Account>) {
+        for (address, mut account) in changes {
+            let storage_cleared = account.is_destroyed || account.storage_cleared;
+            if storage_cleared {
+                self.clear_storage(address);
+            }
+            if account.is_destroyed {
+                let destroyed = CachedAccount {
+                    info: None,
+                    storage_cleared: true,
+                };
+                self.insert_dirty_account(address, destroyed);
+                continue;
+            }
+
+            if let Some(code) = account.info.code.take() {
+                if !code.is_empty() && !self.contracts.contains_key(&code.hash) {
+                    // new code is not in the underlying database.
+                    self.contracts.insert(code.hash, code);
+                }
+            }
+            let previous = self.accounts.get(&address);
+            let cached = CachedAccount {
+                info: Some(account.info),
+                storage_cleared: storage_cleared
+                    || previous.is_some_and(|account| account.storage_cleared),
+            };
+            // unchanged account stays clean and can be evicted.
+            if previous != Some(&cached) {
+                self.insert_dirty_account(address, cached);
+            }
+
+            for (index, slot) in account.storage {
+                if storage_cleared || slot.is_changed() {
+                    self.insert_dirty_slot(address, index, slot.present_value());
+                }
+            }
+        }
+    }
+}
+
+impl<ExtDB: DatabaseRef> Database for LruCacheDB<ExtDB> {
+    type Error = ExtDB::Error;
+
+    fn basic(&mut self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account) = self.accounts.get(&address) {
+            let info = account.info.clone();
+            self.lru.touch(&CacheKey::Account(address));
+            return Ok(info);
+        }
+        let mut info = self.db.basic(address)?;
+        if let Some(code) = info.as_mut().and_then(|info| info.code.take()) {
+            if !code.is_empty() && !self.contracts.contains_key(&code.hash) {
+                self.insert_clean_contract(code);
+            }
+        }
+        self.insert_clean_account(address, info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.contracts.get(&code_hash) {
+            let code = code.clone();
+            self.lru.touch(&CacheKey::Contract(code_hash));
+            return Ok(code);
+        }
+        let code = self.db.code_by_hash(code_hash)?;
+        self.insert_clean_contract(code.clone());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        if let Some(&value) = self.storage.get(&(address, index)) {
+            self.lru.touch(&CacheKey::Storage(address, index));
+            return Ok(value);
+        }
+        if let Some(account) = self.accounts.get(&address) {
+            if account.storage_cleared {
+                return Ok(U256::ZERO);
+            }
+        }
+        let value = self.db.storage(address, index)?;
+        self.insert_clean_slot(address, index, value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<ExtDB: DatabaseRef> DatabaseRef for LruCacheDB<ExtDB> {
+    type Error = ExtDB::Error;
+
+    fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.accounts.get(&address) {
+            Some(account) => Ok(account.info.clone()),
+            None => self.db.basic(address),
+        }
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.contracts.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.db.code_by_hash(code_hash),
+        }
+    }
+
+    fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        if let Some(&value) = self.storage.get(&(address, index)) {
+            return Ok(value);
+        }
+        match self.accounts.get(&address) {
+            Some(account) if account.storage_cleared => Ok(U256::ZERO),
+            _ => self.db.storage(address, index),
+        }
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LruCacheDB, ACCOUNT_SIZE, SLOT_SIZE};
+    use crate::db::{DatabaseCommit, InMemoryDB};
+    use crate::primitives::{Account, AccountInfo, StorageSlot, B176, U256};
+    use crate::Database;
+
+    fn address(byte: u8) -> B176 {
+        B176::repeat_byte(byte)
+    }
+
+    fn remote() -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        for byte in 1..=4 {
+            db.insert_account_info(address(byte), AccountInfo::from_balance(U256::from(byte)));
+            db.insert_account_storage(address(byte), U256::ZERO, U256::from(byte))
+                .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let mut db = LruCacheDB::new(remote(), 2 * ACCOUNT_SIZE);
+        db.basic(address(1)).unwrap();
+        db.basic(address(2)).unwrap();
+        // touch first so second is evicted.
+        db.basic(address(1)).unwrap();
+        db.basic(address(3)).unwrap();
+
+        assert_eq!(db.size(), 2 * ACCOUNT_SIZE);
+        assert!(db.accounts.contains_key(&address(1)));
+        assert!(!db.accounts.contains_key(&address(2)));
+        assert!(db.accounts.contains_key(&address(3)));
+        // evicted account is loaded again.
+        assert_eq!(
+            db.basic(address(2)).unwrap().unwrap().balance,
+            U256::from(2)
+        );
+    }
+
+    #[test]
+    fn test_changes_are_not_evicted() {
+        let mut db = LruCacheDB::new(remote(), ACCOUNT_SIZE + SLOT_SIZE);
+        let mut account = Account::from(db.basic(address(1)).unwrap().unwrap());
+        account.info.balance = U256::from(100);
+        account.storage.insert(
+            U256::ZERO,
+            StorageSlot {
+                original_value: U256::from(1),
+                present_value: U256::from(10),
+            },
+        );
+        db.commit([(address(1), account)].into_iter().collect());
+        assert_eq!(db.size(), 0);
+        assert_eq!(db.dirty_entries(), 2);
+
+        for byte in 2..=4 {
+            db.basic(address(byte)).unwrap();
+            db.storage(address(byte), U256::ZERO).unwrap();
+        }
+        assert!(db.size() <= ACCOUNT_SIZE + SLOT_SIZE);
+        assert_eq!(
+            db.basic(address(1)).unwrap().unwrap().balance,
+            U256::from(100)
+        );
+        assert_eq!(db.storage(address(1), U256::ZERO), Ok(U256::from(10)));
+        assert_eq!(db.dirty_entries(), 2);
+    }
+
+    #[test]
+    fn test_destroyed_storage_is_zero() {
+        let mut db = LruCacheDB::new(remote(), 1024);
+        db.storage(address(1), U256::ZERO).unwrap();
+        let mut account = Account::from(db.basic(address(1)).unwrap().unwrap());
+        account.is_destroyed = true;
+        db.commit([(address(1), account)].into_iter().collect());
+
+        assert_eq!(db.basic(address(1)), Ok(None));
+        assert_eq!(db.storage(address(1), U256::ZERO), Ok(U256::ZERO));
+        assert_eq!(
+            crate::db::DatabaseRef::storage(&db, address(1), U256::ZERO),
+            Ok(U256::ZERO)
+        );
+    }
+}
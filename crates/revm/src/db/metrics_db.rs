@@ -0,0 +1,268 @@
+//! Database wrapper that measures database access.
+//!
+//! [MetricsDB] counts and times calls of each database method, separating first (cold) access
+//! of an account, slot, code or block hash from repeated access of the same one. Every access can
+//! also be passed to a logger.
+use super::{Database, DatabaseRef};
+use crate::primitives::{AccountInfo, Bytecode, B176, B256, U256};
+use crate::resumable::DataRequest;
+use core::fmt;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Logger called after every access with what was read and how long it took.
+pub type AccessLogger = Box<dyn Fn(&DataRequest, Duration) + Send + Sync>;
+
+/// Metrics of a single database method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessMetrics {
+    pub calls: u64,
+    /// Calls that read something not read before.
+    pub cold: u64,
+    pub time: Duration,
+}
+
+impl AccessMetrics {
+    /// Calls that read something that was already read.
+    pub fn repeated(&self) -> u64 {
+        self.calls - self.cold
+    }
+}
+
+/// Access metrics of all database methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetricsReport {
+    pub basic: AccessMetrics,
+    pub code_by_hash: AccessMetrics,
+    pub storage: AccessMetrics,
+    pub block_hash: AccessMetrics,
+}
+
+impl MetricsReport {
+    pub fn total(&self) -> AccessMetrics {
+        [self.basic, self.code_by_hash, self.storage, self.block_hash]
+            .iter()
+            .fold(AccessMetrics::default(), |total, metrics| AccessMetrics {
+                calls: total.calls + metrics.calls,
+                cold: total.cold + metrics.cold,
+                time: total.time + metrics.time,
+            })
+    }
+}
+
+impl fmt::Display for MetricsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<14} {:>10} {:>10} {:>10} {:>14}",
+            "method", "calls", "cold", "repeated", "time"
+        )?;
+        for (name, metrics) in [
+            ("basic", self.basic),
+            ("code_by_hash", self.code_by_hash),
+            ("storage", self.storage),
+            ("block_hash", self.block_hash),
+            ("total", self.total()),
+        ] {
+            writeln!(
+                f,
+                "{:<14} {:>10} {:>10} {:>10} {:>14}",
+                name,
+                metrics.calls,
+                metrics.cold,
+                metrics.repeated(),
+                format!("{:?}", metrics.time)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    report: MetricsReport,
+    accessed: HashSet<DataRequest>,
+}
+
+#[derive(Default)]
+struct Recorder {
+    metrics: Mutex<Metrics>,
+    logger: Option<AccessLogger>,
+}
+
+impl Recorder {
+    /// Time `read` and record it as access of `request`.
+    fn measure<T>(&self, request: DataRequest, read: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = read();
+        let elapsed = start.elapsed();
+        if let Some(logger) = &self.logger {
+            logger(&request, elapsed);
+        }
+
+        let mut metrics = self.metrics.lock().unwrap();
+        let cold = metrics.accessed.insert(request);
+        let report = &mut metrics.report;
+        let method = match request {
+            DataRequest::Account(_) => &mut report.basic,
+            DataRequest::Code(_) => &mut report.code_by_hash,
+            DataRequest::Storage(..) => &mut report.storage,
+            DataRequest::BlockHash(_) => &mut report.block_hash,
+        };
+        method.calls += 1;
+        method.cold += cold as u64;
+        method.time += elapsed;
+        out
+    }
+}
+
+/// Wrapper that records [MetricsReport] of database access, works with [Database]
+/// and [DatabaseRef].
+pub struct MetricsDB<DB> {
+    recorder: Recorder,
+    pub db: DB,
+}
+
+impl<DB> MetricsDB<DB> {
+    pub fn new(db: DB) -> Self {
+        Self {
+            recorder: Recorder::default(),
+            db,
+        }
+    }
+
+    /// Call `logger` on every access.
+    pub fn with_logger(mut self, logger: AccessLogger) -> Self {
+        self.recorder.logger = Some(logger);
+        self
+    }
+
+    pub fn report(&self) -> MetricsReport {
+        self.recorder.metrics.lock().unwrap().report
+    }
+
+    /// Reset metrics, following accesses are cold again.
+    pub fn reset(&self) {
+        *self.recorder.metrics.lock().unwrap() = Metrics::default();
+    }
+}
+
+impl<DB: fmt::Debug> fmt::Debug for MetricsDB<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsDB")
+            .field("metrics", &self.recorder.metrics)
+            .field("logger", &self.recorder.logger.is_some())
+            .field("db", &self.db)
+            .finish()
+    }
+}
+
+impl<DB: Database> Database for MetricsDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        self.recorder
+            .measure(DataRequest::Account(address), || self.db.basic(address))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.recorder.measure(DataRequest::Code(code_hash), || {
+            self.db.code_by_hash(code_hash)
+        })
+    }
+
+    fn storage(&mut self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        self.recorder
+            .measure(DataRequest::Storage(address, index), || {
+                self.db.storage(address, index)
+            })
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.recorder.measure(DataRequest::BlockHash(number), || {
+            self.db.block_hash(number)
+        })
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for MetricsDB<DB> {
+    type Error = DB::Error;
+
+    fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        self.recorder
+            .measure(DataRequest::Account(address), || self.db.basic(address))
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.recorder.measure(DataRequest::Code(code_hash), || {
+            self.db.code_by_hash(code_hash)
+        })
+    }
+
+    fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        self.recorder
+            .measure(DataRequest::Storage(address, index), || {
+                self.db.storage(address, index)
+            })
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.recorder.measure(DataRequest::BlockHash(number), || {
+            self.db.block_hash(number)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetricsDB;
+    use crate::db::{BenchmarkDB, DatabaseRef, InMemoryDB};
+    use crate::primitives::{Bytecode, Bytes, TransactTo, B176, U256};
+    use crate::resumable::DataRequest;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_cold_and_repeated() {
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let log = logged.clone();
+        let db = MetricsDB::new(InMemoryDB::default()).with_logger(Box::new(move |request, _| {
+            log.lock().unwrap().push(*request)
+        }));
+        let address = B176::repeat_byte(1);
+
+        db.basic(address).unwrap();
+        db.basic(address).unwrap();
+        db.storage(address, U256::from(1)).unwrap();
+        db.storage(address, U256::from(2)).unwrap();
+
+        let report = db.report();
+        assert_eq!((report.basic.calls, report.basic.cold), (2, 1));
+        assert_eq!(report.basic.repeated(), 1);
+        assert_eq!((report.storage.calls, report.storage.cold), (2, 2));
+        assert_eq!(report.total().calls, 4);
+        assert_eq!(
+            logged.lock().unwrap()[..2],
+            [DataRequest::Account(address), DataRequest::Account(address)]
+        );
+
+        db.reset();
+        db.basic(address).unwrap();
+        assert_eq!(db.report().basic.cold, 1);
+    }
+
+    #[test]
+    fn test_transact() {
+        let code = Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x54, 0x00]));
+        let mut evm = crate::new();
+        evm.database(MetricsDB::new(BenchmarkDB::new_bytecode(code)));
+        evm.env.tx.caller = B176::repeat_byte(1);
+        evm.env.tx.transact_to = TransactTo::Call(B176::zero());
+        evm.transact().unwrap();
+
+        let report = evm.db().unwrap().report();
+        assert_eq!(report.storage.calls, 1);
+        assert_eq!(report.basic.cold, 2);
+        assert!(report.to_string().contains("storage"));
+    }
+}
@@ -0,0 +1,317 @@
+//! State and block overrides for simulated calls, as in `xcb_call` state override set.
+use super::DatabaseRef;
+use crate::primitives::{AccountInfo, BlockEnv, Bytecode, Bytes, HashMap, B176, B256, U256};
+
+/// Overrides of a single account. Fields that are `None` are read from the underlying database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytes>,
+    /// Replaces whole account storage, slots that are not listed are zero.
+    pub state: Option<HashMap<U256, U256>>,
+    /// Replaces listed storage slots, other slots are read from the underlying database.
+    pub state_diff: Option<HashMap<U256, U256>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OverrideError {
+    /// Account override has both `state` and `state_diff` set.
+    StateAndStateDiff(B176),
+}
+
+/// Overrides of block environment fields, fields that are `None` are left as they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockOverrides {
+    pub number: Option<U256>,
+    pub coinbase: Option<B176>,
+    pub timestamp: Option<U256>,
+    pub difficulty: Option<U256>,
+    pub energy_limit: Option<U256>,
+}
+
+impl BlockOverrides {
+    pub fn apply(&self, block: &mut BlockEnv) {
+        if let Some(number) = self.number {
+            block.number = number;
+        }
+        if let Some(coinbase) = self.coinbase {
+            block.coinbase = coinbase;
+        }
+        if let Some(timestamp) = self.timestamp {
+            block.timestamp = timestamp;
+        }
+        if let Some(difficulty) = self.difficulty {
+            block.difficulty = difficulty;
+        }
+        if let Some(energy_limit) = self.energy_limit {
+            block.energy_limit = energy_limit;
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct OverriddenAccount {
+    balance: Option<U256>,
+    nonce: Option<u64>,
+    code: Option<Bytecode>,
+    storage: HashMap<U256, U256>,
+    /// If set, slots that are not in `storage` are zero.
+    storage_replaced: bool,
+}
+
+/// Read only database that applies account overrides on top of `db`, which is never modified.
+///
+/// Changes from executing on top of it can be kept by wrapping it in [crate::db::CacheDB].
+#[derive(Clone, Debug)]
+pub struct OverrideDB<ExtDB: DatabaseRef> {
+    accounts: HashMap<B176, OverriddenAccount>,
+    contracts: HashMap<B256, Bytecode>,
+    pub db: ExtDB,
+}
+
+impl<ExtDB: DatabaseRef> OverrideDB<ExtDB> {
+    pub fn new(
+        db: ExtDB,
+        overrides: HashMap<B176, AccountOverride>,
+    ) -> Result<Self, OverrideError> {
+        let mut accounts = HashMap::new();
+        let mut contracts = HashMap::new();
+        for (address, account) in overrides {
+            let (storage, storage_replaced) = match (account.state, account.state_diff) {
+                (Some(_), Some(_)) => return Err(OverrideError::StateAndStateDiff(address)),
+                (Some(state), None) => (state, true),
+                (None, diff) => (diff.unwrap_or_default(), false),
+            };
+            let code = account.code.map(|code| {
+                let code = Bytecode::new_raw(code);
+                contracts.insert(code.hash(), code.clone());
+                code
+            });
+            accounts.insert(
+                address,
+                OverriddenAccount {
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code,
+                    storage,
+                    storage_replaced,
+                },
+            );
+        }
+        Ok(Self {
+            accounts,
+            contracts,
+            db,
+        })
+    }
+}
+
+impl<ExtDB: DatabaseRef> DatabaseRef for OverrideDB<ExtDB> {
+    type Error = ExtDB::Error;
+
+    fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        let account = match self.accounts.get(&address) {
+            Some(account) => account,
+            None => return Ok(info),
+        };
+        // overridden account exists even if it is not in the underlying database.
+        let mut info = info.unwrap_or_default();
+        if let Some(balance) = account.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = &account.code {
+            info.code_hash = code.hash();
+            info.code = Some(code.clone());
+        }
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.contracts.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.db.code_by_hash(code_hash),
+        }
+    }
+
+    fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        if let Some(account) = self.accounts.get(&address) {
+            if let Some(value) = account.storage.get(&index) {
+                return Ok(*value);
+            }
+            if account.storage_replaced {
+                return Ok(U256::ZERO);
+            }
+        }
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountOverride, BlockOverrides, OverrideDB, OverrideError};
+    use crate::db::{DatabaseRef, InMemoryDB};
+    use crate::interpreter::opcode;
+    use crate::primitives::{
+        hex_literal::hex, AccountInfo, BlockEnv, Bytes, ExecutionResult, HashMap, Output,
+        TransactTo, B176, U256,
+    };
+
+    fn overrides(address: B176, account: AccountOverride) -> HashMap<B176, AccountOverride> {
+        [(address, account)].into_iter().collect()
+    }
+
+    #[test]
+    fn test_state_and_state_diff() {
+        let address = B176(hex!("00000000000000000000000000000000000000000100"));
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(address, AccountInfo::from_balance(U256::from(10)));
+        for slot in 1..=2 {
+            db.insert_account_storage(address, U256::from(slot), U256::from(slot))
+                .unwrap();
+        }
+        let slots: HashMap<_, _> = [(U256::from(1), U256::from(100))].into_iter().collect();
+
+        let state_diff = OverrideDB::new(
+            &db,
+            overrides(
+                address,
+                AccountOverride {
+                    state_diff: Some(slots.clone()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            state_diff.storage(address, U256::from(1)),
+            Ok(U256::from(100))
+        );
+        assert_eq!(
+            state_diff.storage(address, U256::from(2)),
+            Ok(U256::from(2))
+        );
+
+        let state = OverrideDB::new(
+            &db,
+            overrides(
+                address,
+                AccountOverride {
+                    state: Some(slots.clone()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .unwrap();
+        assert_eq!(state.storage(address, U256::from(1)), Ok(U256::from(100)));
+        assert_eq!(state.storage(address, U256::from(2)), Ok(U256::ZERO));
+        assert_eq!(
+            state.basic(address).unwrap().unwrap().balance,
+            U256::from(10)
+        );
+
+        let both = OverrideDB::new(
+            &db,
+            overrides(
+                address,
+                AccountOverride {
+                    state: Some(slots.clone()),
+                    state_diff: Some(slots),
+                    ..Default::default()
+                },
+            ),
+        );
+        assert_eq!(both.err(), Some(OverrideError::StateAndStateDiff(address)));
+    }
+
+    #[test]
+    fn test_call_with_overrides() {
+        let caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        // return NUMBER + SLOAD(0)
+        let code = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::SLOAD,
+            opcode::NUMBER,
+            opcode::ADD,
+            opcode::PUSH1,
+            0x0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x0,
+            opcode::RETURN,
+        ]);
+        let db = InMemoryDB::default();
+        let db = OverrideDB::new(
+            db,
+            [
+                (
+                    caller,
+                    AccountOverride {
+                        balance: Some(U256::from(1_000_000)),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    contract,
+                    AccountOverride {
+                        code: Some(code),
+                        state_diff: Some([(U256::ZERO, U256::from(5))].into_iter().collect()),
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap();
+
+        let mut evm = crate::new();
+        evm.env.tx.caller = caller;
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+        evm.env.tx.energy_limit = 100_000;
+        BlockOverrides {
+            number: Some(U256::from(7)),
+            ..Default::default()
+        }
+        .apply(&mut evm.env.block);
+        evm.database(db);
+
+        let output = match evm.transact_ref().unwrap().result {
+            ExecutionResult::Success {
+                output: Output::Call(output),
+                ..
+            } => output,
+            result => panic!("unexpected result {result:?}"),
+        };
+        assert_eq!(U256::from_be_slice(&output), U256::from(12));
+        // underlying database is not modified.
+        assert_eq!(evm.db().unwrap().db.basic(caller), Ok(None));
+    }
+
+    #[test]
+    fn test_block_overrides() {
+        let mut block = BlockEnv::default();
+        let coinbase = B176(hex!("00000000000000000000000000000000000000000300"));
+        BlockOverrides {
+            timestamp: Some(U256::from(42)),
+            coinbase: Some(coinbase),
+            ..Default::default()
+        }
+        .apply(&mut block);
+        assert_eq!(block.timestamp, U256::from(42));
+        assert_eq!(block.coinbase, coinbase);
+        assert_eq!(block.number, BlockEnv::default().number);
+    }
+}
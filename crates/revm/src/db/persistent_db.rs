@@ -0,0 +1,393 @@
+//! State database persisted on disk with [redb].
+//!
+//! Every [DatabaseCommit::commit] is written in a single redb write transaction, so either all
+//! changes of the commit are on disk or none of them are, and database reopened after a crash
+//! contains state of the last successful commit.
+use super::{DatabaseCommit, DatabaseRef};
+use crate::primitives::{
+    db::Database, Account, AccountInfo, Bytecode, HashMap, B176, B256, SHA3_EMPTY, U256,
+};
+use redb::{ReadableTable, TableDefinition};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum PersistentDBError {
+    /// Error of the underlying redb database, boxed as it is large.
+    Redb(Box<redb::Error>),
+    /// Bytecode of the code hash is not stored, account refers to a missing contract.
+    MissingCode(B256),
+}
+
+macro_rules! impl_from_redb_error {
+    ($($error:ty),*) => {
+        $(impl From<$error> for PersistentDBError {
+            fn from(error: $error) -> Self {
+                Self::Redb(Box::new(error.into()))
+            }
+        })*
+    };
+}
+
+impl_from_redb_error!(
+    redb::Error,
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError
+);
+
+impl core::fmt::Display for PersistentDBError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Redb(error) => error.fmt(f),
+            Self::MissingCode(code_hash) => write!(f, "missing code of hash {code_hash:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PersistentDBError {}
+
+/// Address to balance (32 bytes), nonce (8 bytes) and code hash (32 bytes).
+const ACCOUNTS: TableDefinition<[u8; 22], [u8; 72]> = TableDefinition::new("accounts");
+/// Address followed by slot index to the slot value. Zero values are not stored.
+const STORAGE: TableDefinition<[u8; 54], [u8; 32]> = TableDefinition::new("storage");
+/// Code hash to original bytecode.
+const CONTRACTS: TableDefinition<[u8; 32], &[u8]> = TableDefinition::new("contracts");
+/// Block number to block hash.
+const BLOCK_HASHES: TableDefinition<[u8; 32], [u8; 32]> = TableDefinition::new("block_hashes");
+
+/// Database that keeps accounts, storage, contracts and block hashes in a file.
+///
+/// Accounts, storage and block hashes that are not in the file are reported as empty, missing
+/// bytecode is an error.
+pub struct PersistentDB {
+    db: redb::Database,
+}
+
+impl PersistentDB {
+    /// Open database at `path`, creating the file if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PersistentDBError> {
+        let db = redb::Database::create(path)?;
+        // create tables so that read transactions can always open them.
+        let tx = db.begin_write()?;
+        tx.open_table(ACCOUNTS)?;
+        tx.open_table(STORAGE)?;
+        tx.open_table(CONTRACTS)?;
+        tx.open_table(BLOCK_HASHES)?;
+        tx.commit()?;
+        Ok(Self { db })
+    }
+
+    /// Insert account info, its code is inserted too if it is present.
+    pub fn insert_account_info(
+        &mut self,
+        address: B176,
+        info: AccountInfo,
+    ) -> Result<(), PersistentDBError> {
+        let tx = self.db.begin_write()?;
+        {
+            let mut accounts = tx.open_table(ACCOUNTS)?;
+            let mut contracts = tx.open_table(CONTRACTS)?;
+            write_account(&mut accounts, &mut contracts, address, &info)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn insert_account_storage(
+        &mut self,
+        address: B176,
+        slot: U256,
+        value: U256,
+    ) -> Result<(), PersistentDBError> {
+        let tx = self.db.begin_write()?;
+        {
+            let mut storage = tx.open_table(STORAGE)?;
+            write_slot(&mut storage, address, slot, value)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn insert_block_hash(&mut self, number: U256, hash: B256) -> Result<(), PersistentDBError> {
+        let tx = self.db.begin_write()?;
+        tx.open_table(BLOCK_HASHES)?
+            .insert(number.to_be_bytes::<{ U256::BYTES }>(), hash.0)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Apply changes atomically, on error nothing is written.
+    pub fn try_commit(&mut self, changes: HashMap<B176, Account>) -> Result<(), PersistentDBError> {
+        let tx = self.db.begin_write()?;
+        {
+            let mut accounts = tx.open_table(ACCOUNTS)?;
+            let mut storage = tx.open_table(STORAGE)?;
+            let mut contracts = tx.open_table(CONTRACTS)?;
+            for (address, account) in changes {
+                if account.is_destroyed {
+                    accounts.remove(address.0)?;
+                    clear_storage(&mut storage, address)?;
+                    continue;
+                }
+                write_account(&mut accounts, &mut contracts, address, &account.info)?;
+                if account.storage_cleared {
+                    clear_storage(&mut storage, address)?;
+                }
+                for (slot, value) in account.storage {
+                    write_slot(&mut storage, address, slot, value.present_value())?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn write_account(
+    accounts: &mut redb::Table<[u8; 22], [u8; 72]>,
+    contracts: &mut redb::Table<[u8; 32], &[u8]>,
+    address: B176,
+    info: &AccountInfo,
+) -> Result<(), PersistentDBError> {
+    if let Some(code) = &info.code {
+        if !code.is_empty() {
+            contracts.insert(info.code_hash.0, code.original_bytes().as_ref())?;
+        }
+    }
+    let mut value = [0u8; 72];
+    value[..32].copy_from_slice(&info.balance.to_be_bytes::<{ U256::BYTES }>());
+    value[32..40].copy_from_slice(&info.nonce.to_be_bytes());
+    value[40..].copy_from_slice(&info.code_hash.0);
+    accounts.insert(address.0, value)?;
+    Ok(())
+}
+
+fn write_slot(
+    storage: &mut redb::Table<[u8; 54], [u8; 32]>,
+    address: B176,
+    slot: U256,
+    value: U256,
+) -> Result<(), PersistentDBError> {
+    let key = storage_key(address, slot);
+    if value == U256::ZERO {
+        storage.remove(key)?;
+    } else {
+        storage.insert(key, value.to_be_bytes::<{ U256::BYTES }>())?;
+    }
+    Ok(())
+}
+
+fn clear_storage(
+    storage: &mut redb::Table<[u8; 54], [u8; 32]>,
+    address: B176,
+) -> Result<(), PersistentDBError> {
+    let range = storage_key(address, U256::ZERO)..=storage_key(address, U256::MAX);
+    let mut keys = Vec::new();
+    for entry in storage.range(range)? {
+        keys.push(entry?.0.value());
+    }
+    for key in keys {
+        storage.remove(key)?;
+    }
+    Ok(())
+}
+
+fn storage_key(address: B176, slot: U256) -> [u8; 54] {
+    let mut key = [0u8; 54];
+    key[..22].copy_from_slice(&address.0);
+    key[22..].copy_from_slice(&slot.to_be_bytes::<{ U256::BYTES }>());
+    key
+}
+
+impl DatabaseRef for PersistentDB {
+    type Error = PersistentDBError;
+
+    fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        let tx = self.db.begin_read()?;
+        let accounts = tx.open_table(ACCOUNTS)?;
+        let value = match accounts.get(address.0)? {
+            Some(value) => value.value(),
+            None => return Ok(None),
+        };
+        Ok(Some(AccountInfo {
+            balance: U256::from_be_bytes::<{ U256::BYTES }>(value[..32].try_into().unwrap()),
+            nonce: u64::from_be_bytes(value[32..40].try_into().unwrap()),
+            code_hash: B256::from_slice(&value[40..]),
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == SHA3_EMPTY {
+            return Ok(Bytecode::new());
+        }
+        let tx = self.db.begin_read()?;
+        let contracts = tx.open_table(CONTRACTS)?;
+        match contracts.get(code_hash.0)? {
+            // SAFETY: code is stored under its hash.
+            Some(code) => {
+                Ok(unsafe { Bytecode::new_raw_with_hash(code.value().to_vec().into(), code_hash) })
+            }
+            None => Err(PersistentDBError::MissingCode(code_hash)),
+        }
+    }
+
+    fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        let tx = self.db.begin_read()?;
+        let storage = tx.open_table(STORAGE)?;
+        let value = storage
+            .get(storage_key(address, index))?
+            .map(|value| U256::from_be_bytes(value.value()))
+            .unwrap_or_default();
+        Ok(value)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        let tx = self.db.begin_read()?;
+        let block_hashes = tx.open_table(BLOCK_HASHES)?;
+        let hash = block_hashes
+            .get(number.to_be_bytes::<{ U256::BYTES }>())?
+            .map(|hash| B256(hash.value()))
+            .unwrap_or_default();
+        Ok(hash)
+    }
+}
+
+impl Database for PersistentDB {
+    type Error = PersistentDBError;
+
+    fn basic(&mut self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        <Self as DatabaseRef>::basic(self, address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        <Self as DatabaseRef>::code_by_hash(self, code_hash)
+    }
+
+    fn storage(&mut self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        <Self as DatabaseRef>::storage(self, address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        <Self as DatabaseRef>::block_hash(self, number)
+    }
+}
+
+impl DatabaseCommit for PersistentDB {
+    /// # Panics
+    ///
+    /// If changes can't be written, use [PersistentDB::try_commit] to handle the error.
+    fn commit(&mut self, changes: HashMap<B176, Account>) {
+        self.try_commit(changes)
+            .expect("persistent database commit failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PersistentDB, PersistentDBError};
+    use crate::db::DatabaseRef;
+    use crate::interpreter::opcode;
+    use crate::primitives::{
+        hex_literal::hex, Account, AccountInfo, Bytecode, Bytes, TransactTo, B176, B256, U256,
+    };
+    use std::path::PathBuf;
+
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("revm-{name}-{}.redb", std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_commit_and_reopen() {
+        let path = TempPath::new("commit-and-reopen");
+        let caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        // store 0x2a to slot 1 and clear slot 2
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x2a,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x2,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+        let code_hash = code.hash();
+        let info = AccountInfo::new(U256::ZERO, 1, code.clone());
+
+        let mut db = PersistentDB::open(&path.0).unwrap();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)))
+            .unwrap();
+        db.insert_account_info(contract, info).unwrap();
+        db.insert_account_storage(contract, U256::from(2), U256::from(7))
+            .unwrap();
+        db.insert_block_hash(U256::from(1), B256::repeat_byte(1))
+            .unwrap();
+
+        let mut evm = crate::new();
+        evm.env.tx.caller = caller;
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+        evm.env.tx.energy_limit = 100_000;
+        evm.env.tx.energy_price = U256::from(1);
+        evm.database(db);
+        let result = evm.transact_commit().unwrap();
+        assert!(result.is_success());
+        drop(evm);
+
+        let mut db = PersistentDB::open(&path.0).unwrap();
+        let caller_info = db.basic(caller).unwrap().unwrap();
+        assert_eq!(caller_info.nonce, 1);
+        assert_eq!(
+            caller_info.balance,
+            U256::from(1_000_000 - result.energy_used())
+        );
+        assert_eq!(db.basic(contract).unwrap().unwrap().code_hash, code_hash);
+        assert_eq!(
+            db.storage(contract, U256::from(1)).unwrap(),
+            U256::from(0x2a)
+        );
+        assert_eq!(db.storage(contract, U256::from(2)).unwrap(), U256::ZERO);
+        assert_eq!(
+            db.code_by_hash(code_hash).unwrap().original_bytes(),
+            code.original_bytes()
+        );
+        assert_eq!(db.block_hash(U256::from(1)).unwrap(), B256::repeat_byte(1));
+
+        let mut destroyed = Account::from(AccountInfo::default());
+        destroyed.is_destroyed = true;
+        db.try_commit([(contract, destroyed)].into_iter().collect())
+            .unwrap();
+        assert_eq!(db.basic(contract).unwrap(), None);
+        assert_eq!(db.storage(contract, U256::from(1)).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_missing_code() {
+        let path = TempPath::new("missing-code");
+        let db = PersistentDB::open(&path.0).unwrap();
+        let code_hash = B256::repeat_byte(0xc0);
+        assert!(matches!(
+            db.code_by_hash(code_hash),
+            Err(PersistentDBError::MissingCode(hash)) if hash == code_hash
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+//! Read-through cache that can be shared between threads.
+//!
+//! [SharedCacheDB] caches everything loaded from the underlying database and implements
+//! [DatabaseRef], so it can be used by many EVMs at the same time behind [Arc] or a reference.
+//! It is never written to, changes of each EVM are kept in its own [crate::db::CacheDB]
+//! on top of it.
+//!
+//! [Arc]: std::sync::Arc
+use super::DatabaseRef;
+use crate::primitives::{AccountInfo, Bytecode, HashMap, B176, B256, U256};
+use core::hash::{BuildHasher, Hash};
+use std::collections::hash_map::RandomState;
+use std::sync::RwLock;
+
+/// Number of independently locked parts of each map.
+const SHARDS: usize = 16;
+
+/// Map split into shards by key hash so that threads accessing different keys rarely block
+/// each other.
+#[derive(Debug)]
+struct ShardedMap<K, V> {
+    hasher: RandomState,
+    shards: [RwLock<HashMap<K, V>>; SHARDS],
+}
+
+impl<K: Hash + Eq, V: Clone> ShardedMap<K, V> {
+    fn new() -> Self {
+        Self {
+            hasher: RandomState::new(),
+            shards: core::array::from_fn(|_| RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>> {
+        &self.shards[self.hasher.hash_one(key) as usize % SHARDS]
+    }
+
+    /// Return cached value or load and cache it. Lock is not held while loading, if two threads
+    /// load the same key the value loaded first is kept.
+    fn get_or_load<E>(&self, key: K, load: impl FnOnce() -> Result<V, E>) -> Result<V, E> {
+        let shard = self.shard(&key);
+        if let Some(value) = shard.read().unwrap().get(&key) {
+            return Ok(value.clone());
+        }
+        let value = load()?;
+        Ok(shard.write().unwrap().entry(key).or_insert(value).clone())
+    }
+
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().len())
+            .sum()
+    }
+}
+
+/// Thread-safe read-through cache over [DatabaseRef].
+#[derive(Debug)]
+pub struct SharedCacheDB<ExtDB: DatabaseRef> {
+    accounts: ShardedMap<B176, Option<AccountInfo>>,
+    storage: ShardedMap<(B176, U256), U256>,
+    contracts: ShardedMap<B256, Bytecode>,
+    block_hashes: ShardedMap<U256, B256>,
+    pub db: ExtDB,
+}
+
+impl<ExtDB: DatabaseRef> SharedCacheDB<ExtDB> {
+    pub fn new(db: ExtDB) -> Self {
+        Self {
+            accounts: ShardedMap::new(),
+            storage: ShardedMap::new(),
+            contracts: ShardedMap::new(),
+            block_hashes: ShardedMap::new(),
+            db,
+        }
+    }
+
+    /// Number of cached accounts, storage slots, contracts and block hashes.
+    pub fn len(&self) -> usize {
+        self.accounts.len() + self.storage.len() + self.contracts.len() + self.block_hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<ExtDB: DatabaseRef> DatabaseRef for SharedCacheDB<ExtDB> {
+    type Error = ExtDB::Error;
+
+    fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+        self.accounts
+            .get_or_load(address, || self.db.basic(address))
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.contracts
+            .get_or_load(code_hash, || self.db.code_by_hash(code_hash))
+    }
+
+    fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+        self.storage
+            .get_or_load((address, index), || self.db.storage(address, index))
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.block_hashes
+            .get_or_load(number, || self.db.block_hash(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedCacheDB;
+    use crate::db::{CacheDB, DatabaseRef, InMemoryDB};
+    use crate::interpreter::opcode;
+    use crate::primitives::{
+        hex_literal::hex, AccountInfo, Bytecode, Bytes, TransactTo, B176, B256, U256,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Database that counts how many times it was read.
+    struct Counting {
+        db: InMemoryDB,
+        reads: AtomicUsize,
+    }
+
+    impl DatabaseRef for Counting {
+        type Error = <InMemoryDB as DatabaseRef>::Error;
+
+        fn basic(&self, address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.db.basic(address)
+        }
+
+        fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.db.code_by_hash(code_hash)
+        }
+
+        fn storage(&self, address: B176, index: U256) -> Result<U256, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.db.storage(address, index)
+        }
+
+        fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.db.block_hash(number)
+        }
+    }
+
+    #[test]
+    fn test_parallel_workers() {
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        // increment slot 0
+        let code = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x1,
+            opcode::ADD,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, Bytecode::new_raw(code)),
+        );
+        db.insert_account_storage(contract, U256::ZERO, U256::from(41))
+            .unwrap();
+        let callers: Vec<B176> = (1..=4).map(B176::repeat_byte).collect();
+        for caller in &callers {
+            db.insert_account_info(*caller, AccountInfo::from_balance(U256::from(1_000_000)));
+        }
+
+        let shared = SharedCacheDB::new(Counting {
+            db,
+            reads: AtomicUsize::new(0),
+        });
+        let run = |caller: B176| {
+            let mut evm = crate::new();
+            evm.env.tx.caller = caller;
+            evm.env.tx.transact_to = TransactTo::Call(contract);
+            evm.env.tx.energy_limit = 100_000;
+            evm.database(CacheDB::new(&shared));
+            // each worker commits to its own layer.
+            for _ in 0..2 {
+                assert!(evm.transact_commit().unwrap().is_success());
+                evm.env.tx.nonce = Some(1);
+            }
+            evm.db().unwrap().storage(contract, U256::ZERO).unwrap()
+        };
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = callers
+                .iter()
+                .map(|caller| scope.spawn(move || run(*caller)))
+                .collect();
+            for worker in workers {
+                assert_eq!(worker.join().unwrap(), U256::from(43));
+            }
+        });
+
+        let reads = shared.db.reads.load(Ordering::Relaxed);
+        assert!(!shared.is_empty());
+        run(callers[0]);
+        // everything is already cached.
+        assert_eq!(shared.db.reads.load(Ordering::Relaxed), reads);
+        assert_eq!(shared.storage(contract, U256::ZERO), Ok(U256::from(41)));
+    }
+}
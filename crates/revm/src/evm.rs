@@ -1,11 +1,13 @@
 use crate::primitives::{specification, EVMError, EVMResult, Env, ExecutionResult, SpecId};
 use crate::{
+    bytecode_cache::BytecodeCache,
     db::{Database, DatabaseCommit, DatabaseRef, RefDBWrapper},
     evm_impl::{EVMImpl, Transact},
     inspectors::NoOpInspector,
+    resumable::ResumableTransact,
     Inspector,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, sync::Arc};
 use revm_interpreter::primitives::ResultAndState;
 use revm_precompile::Precompiles;
 
@@ -29,6 +31,8 @@ use revm_precompile::Precompiles;
 pub struct EVM<DB> {
     pub env: Env,
     pub db: Option<DB>,
+    /// Analysed bytecode cache, can be shared between EVMs.
+    pub bytecode_cache: Option<Arc<BytecodeCache>>,
 }
 
 pub fn new<DB>() -> EVM<DB> {
@@ -64,7 +68,13 @@ impl<DB: Database> EVM<DB> {
     pub fn transact(&mut self) -> EVMResult<DB::Error> {
         if let Some(db) = self.db.as_mut() {
             let mut noop = NoOpInspector {};
-            let out = evm_inner::<DB, false>(&mut self.env, db, &mut noop).transact();
+            let out = evm_inner_with_cache::<DB, false>(
+                &mut self.env,
+                db,
+                &mut noop,
+                self.bytecode_cache.as_deref(),
+            )
+            .transact();
             out
         } else {
             panic!("Database needs to be set");
@@ -74,7 +84,34 @@ impl<DB: Database> EVM<DB> {
     /// Execute transaction with given inspector, without wring to DB. Return change state.
     pub fn inspect<INSP: Inspector<DB>>(&mut self, mut inspector: INSP) -> EVMResult<DB::Error> {
         if let Some(db) = self.db.as_mut() {
-            evm_inner::<DB, true>(&mut self.env, db, &mut inspector).transact()
+            evm_inner_with_cache::<DB, true>(
+                &mut self.env,
+                db,
+                &mut inspector,
+                self.bytecode_cache.as_deref(),
+            )
+            .transact()
+        } else {
+            panic!("Database needs to be set");
+        }
+    }
+}
+
+impl<DB: Database> EVM<DB> {
+    /// Start transaction that is suspended instead of failing when database returns an error.
+    /// Result is not written to DB. See [crate::resumable].
+    pub fn transact_resumable(&mut self) -> Box<dyn ResumableTransact<DB> + '_> {
+        // inspector is zero sized so leaking it does not allocate.
+        self.inspect_resumable(Box::leak(Box::new(NoOpInspector {})))
+    }
+
+    /// Start resumable transaction with given inspector. Result is not written to DB.
+    pub fn inspect_resumable<'a, INSP: Inspector<DB>>(
+        &'a mut self,
+        inspector: &'a mut INSP,
+    ) -> Box<dyn ResumableTransact<DB> + 'a> {
+        if let Some(db) = self.db.as_mut() {
+            evm_inner_resumable::<DB>(&mut self.env, db, inspector, self.bytecode_cache.as_deref())
         } else {
             panic!("Database needs to be set");
         }
@@ -88,9 +125,13 @@ impl<'a, DB: DatabaseRef> EVM<DB> {
             let mut noop = NoOpInspector {};
             let mut db = RefDBWrapper::new(db);
             let db = &mut db;
-            let out =
-                evm_inner::<RefDBWrapper<DB::Error>, false>(&mut self.env.clone(), db, &mut noop)
-                    .transact();
+            let out = evm_inner_with_cache::<RefDBWrapper<DB::Error>, false>(
+                &mut self.env.clone(),
+                db,
+                &mut noop,
+                self.bytecode_cache.as_deref(),
+            )
+            .transact();
             out
         } else {
             panic!("Database needs to be set");
@@ -105,10 +146,11 @@ impl<'a, DB: DatabaseRef> EVM<DB> {
         if let Some(db) = self.db.as_ref() {
             let mut db = RefDBWrapper::new(db);
             let db = &mut db;
-            let out = evm_inner::<RefDBWrapper<DB::Error>, true>(
+            let out = evm_inner_with_cache::<RefDBWrapper<DB::Error>, true>(
                 &mut self.env.clone(),
                 db,
                 &mut inspector,
+                self.bytecode_cache.as_deref(),
             )
             .transact();
             out
@@ -126,7 +168,16 @@ impl<DB> EVM<DB> {
 
     /// Creates a new [EVM] instance with the given environment.
     pub fn with_env(env: Env) -> Self {
-        Self { env, db: None }
+        Self {
+            env,
+            db: None,
+            bytecode_cache: None,
+        }
+    }
+
+    /// Set analysed bytecode cache, it can be shared with other EVMs.
+    pub fn bytecode_cache(&mut self, cache: Arc<BytecodeCache>) {
+        self.bytecode_cache = Some(cache);
     }
 
     pub fn database(&mut self, db: DB) {
@@ -143,15 +194,74 @@ impl<DB> EVM<DB> {
 }
 
 macro_rules! create_evm {
-    ($spec:ident, $db:ident,$env:ident,$inspector:ident,$network:expr) => {
+    ($spec:ident, $db:ident,$env:ident,$inspector:ident,$network:expr,$cache:ident) => {
         Box::new(EVMImpl::<'a, $spec, DB, INSPECT>::new(
             $db,
             $env,
             $inspector,
             Precompiles::new(to_precompile_id($spec::SPEC_ID)).clone(),
             $network,
+            $cache,
         )) as Box<dyn Transact<DB::Error> + 'a>
     };
+    ($spec:ident, $db:ident,$env:ident,$inspector:ident,$network:expr,$cache:ident,
+        resumable $($bound:ident)?) => {
+        Box::new(
+            EVMImpl::<'a, $spec, DB, true, _>::new(
+                $db,
+                $env,
+                $inspector,
+                Precompiles::new(to_precompile_id($spec::SPEC_ID)).clone(),
+                $network,
+                $cache,
+            )
+            .resumable(),
+        ) as Box<dyn ResumableTransact<DB> $(+ $bound)? + 'a>
+    };
+}
+
+/// Create resumable execution for the spec of the environment, `bound` is added to the returned
+/// trait object.
+macro_rules! create_resumable_evm {
+    ($db:ident, $env:ident, $insp:ident, $cache:ident $(, $bound:ident)?) => {{
+        use specification::*;
+        let network = $env.cfg.network_id;
+        match $env.cfg.spec_id {
+            SpecId::FRONTIER | SpecId::FRONTIER_THAWING => {
+                create_evm!(FrontierSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::HOMESTEAD | SpecId::DAO_FORK => {
+                create_evm!(HomesteadSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::TANGERINE => {
+                create_evm!(TangerineSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::SPURIOUS_DRAGON => {
+                create_evm!(SpuriousDragonSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::BYZANTIUM => {
+                create_evm!(ByzantiumSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::PETERSBURG | SpecId::CONSTANTINOPLE => {
+                create_evm!(PetersburgSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::ISTANBUL => {
+                create_evm!(IstanbulSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::CVM_ED448 => {
+                create_evm!(CvmEd448Spec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::CVM_MODEXP => {
+                create_evm!(CvmModexpSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::CVM_BLS => {
+                create_evm!(CvmBlsSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+            SpecId::LATEST => {
+                create_evm!(LatestSpec, $db, $env, $insp, network, $cache, resumable $($bound)?)
+            }
+        }
+    }};
 }
 
 pub fn to_precompile_id(spec_id: SpecId) -> revm_precompile::SpecId {
@@ -166,7 +276,10 @@ pub fn to_precompile_id(spec_id: SpecId) -> revm_precompile::SpecId {
             revm_precompile::SpecId::BYZANTIUM
         }
         SpecId::ISTANBUL => revm_precompile::SpecId::ISTANBUL,
-        SpecId::LATEST => revm_precompile::SpecId::ISTANBUL,
+        SpecId::CVM_ED448 => revm_precompile::SpecId::CVM_ED448,
+        SpecId::CVM_MODEXP => revm_precompile::SpecId::CVM_MODEXP,
+        SpecId::CVM_BLS => revm_precompile::SpecId::CVM_BLS,
+        SpecId::LATEST => revm_precompile::SpecId::LATEST,
     }
 }
 
@@ -174,23 +287,61 @@ pub fn evm_inner<'a, DB: Database, const INSPECT: bool>(
     env: &'a mut Env,
     db: &'a mut DB,
     insp: &'a mut dyn Inspector<DB>,
+) -> Box<dyn Transact<DB::Error> + 'a> {
+    evm_inner_with_cache::<DB, INSPECT>(env, db, insp, None)
+}
+
+pub fn evm_inner_with_cache<'a, DB: Database, const INSPECT: bool>(
+    env: &'a mut Env,
+    db: &'a mut DB,
+    insp: &'a mut dyn Inspector<DB>,
+    cache: Option<&'a BytecodeCache>,
 ) -> Box<dyn Transact<DB::Error> + 'a> {
     use specification::*;
     let network = env.cfg.network_id;
     match env.cfg.spec_id {
         SpecId::FRONTIER | SpecId::FRONTIER_THAWING => {
-            create_evm!(FrontierSpec, db, env, insp, network)
+            create_evm!(FrontierSpec, db, env, insp, network, cache)
+        }
+        SpecId::HOMESTEAD | SpecId::DAO_FORK => {
+            create_evm!(HomesteadSpec, db, env, insp, network, cache)
         }
-        SpecId::HOMESTEAD | SpecId::DAO_FORK => create_evm!(HomesteadSpec, db, env, insp, network),
-        SpecId::TANGERINE => create_evm!(TangerineSpec, db, env, insp, network),
-        SpecId::SPURIOUS_DRAGON => create_evm!(SpuriousDragonSpec, db, env, insp, network),
-        SpecId::BYZANTIUM => create_evm!(ByzantiumSpec, db, env, insp, network),
+        SpecId::TANGERINE => create_evm!(TangerineSpec, db, env, insp, network, cache),
+        SpecId::SPURIOUS_DRAGON => create_evm!(SpuriousDragonSpec, db, env, insp, network, cache),
+        SpecId::BYZANTIUM => create_evm!(ByzantiumSpec, db, env, insp, network, cache),
         SpecId::PETERSBURG | SpecId::CONSTANTINOPLE => {
-            create_evm!(PetersburgSpec, db, env, insp, network)
+            create_evm!(PetersburgSpec, db, env, insp, network, cache)
         }
         SpecId::ISTANBUL => {
-            create_evm!(IstanbulSpec, db, env, insp, network)
+            create_evm!(IstanbulSpec, db, env, insp, network, cache)
         }
-        SpecId::LATEST => create_evm!(LatestSpec, db, env, insp, network),
+        SpecId::CVM_ED448 => create_evm!(CvmEd448Spec, db, env, insp, network, cache),
+        SpecId::CVM_MODEXP => create_evm!(CvmModexpSpec, db, env, insp, network, cache),
+        SpecId::CVM_BLS => create_evm!(CvmBlsSpec, db, env, insp, network, cache),
+        SpecId::LATEST => create_evm!(LatestSpec, db, env, insp, network, cache),
     }
 }
+
+/// Create resumable execution, it always runs with inspector hooks enabled.
+pub fn evm_inner_resumable<'a, DB: Database>(
+    env: &'a mut Env,
+    db: &'a mut DB,
+    insp: &'a mut dyn Inspector<DB>,
+    cache: Option<&'a BytecodeCache>,
+) -> Box<dyn ResumableTransact<DB> + 'a> {
+    create_resumable_evm!(db, env, insp, cache)
+}
+
+/// Create resumable execution that can be moved between threads while it is suspended,
+/// e.g. held by a future across `.await`.
+pub fn evm_inner_resumable_send<'a, DB: Database + Send>(
+    env: &'a mut Env,
+    db: &'a mut DB,
+    insp: &'a mut (dyn Inspector<DB> + Send),
+    cache: Option<&'a BytecodeCache>,
+) -> Box<dyn ResumableTransact<DB> + Send + 'a>
+where
+    DB::Error: Send,
+{
+    create_resumable_evm!(db, env, insp, cache, Send)
+}
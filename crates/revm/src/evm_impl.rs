@@ -1,18 +1,27 @@
+use crate::interpreter::opcode;
 use crate::interpreter::{
     analysis::to_analysed, energy, instruction_result::SuccessOrHalt, return_ok, return_revert,
     CallContext, CallInputs, CallScheme, Contract, CreateInputs, CreateScheme, Host,
-    InstructionResult, Interpreter, SelfDestructResult, Transfer, CALL_STACK_LIMIT,
+    InstructionResult, Interpreter, InterpreterAction, SelfDestructResult, Transfer,
+    CALL_STACK_LIMIT,
 };
 use crate::primitives::{
     create2_address, create_address, sha3, Account, AnalysisKind, Bytecode, Bytes, EVMError,
-    EVMResult, Env, ExecutionResult, HashMap, InvalidTransaction, Log, Output, ResultAndState,
-    Spec,
+    EVMResult, Env, ExecutionResult, Halt, HashMap, InvalidTransaction, Log, Output,
+    PrecompileError, ResultAndState, Spec,
     SpecId::{self, *},
     TransactTo, B176, B256, SHA3_EMPTY, U256,
 };
-use crate::{db::Database, journaled_state::JournaledState, precompile, Inspector};
-use alloc::vec::Vec;
-use core::{cmp::min, marker::PhantomData};
+use crate::{
+    bytecode_cache::BytecodeCache,
+    db::Database,
+    journaled_state::{JournalCheckpoint, JournaledState},
+    precompile,
+    resumable::{DataRequest, ResumableTransact, ResumeResult},
+    Inspector,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{cmp::min, marker::PhantomData, ops::Range};
 use revm_interpreter::energy::Energy;
 use revm_interpreter::primitives::Network;
 use revm_interpreter::MAX_CODE_SIZE;
@@ -24,26 +33,362 @@ pub struct EVMData<'a, DB: Database> {
     pub journaled_state: JournaledState,
     pub db: &'a mut DB,
     pub error: Option<DB::Error>,
+    /// Error of the precompile call that is ending, inspectors can read it in `call_end`
+    /// when the call returned [InstructionResult::PrecompileError]. It is moved into the
+    /// frame result after `call_end`.
+    pub precompile_error: Option<PrecompileError>,
 }
 
-pub struct EVMImpl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> {
+pub struct EVMImpl<
+    'a,
+    GSPEC: Spec,
+    DB: Database,
+    const INSPECT: bool,
+    INSP: Inspector<DB> + ?Sized = dyn Inspector<DB> + 'a,
+> {
     data: EVMData<'a, DB>,
     precompiles: Precompiles,
-    inspector: &'a mut dyn Inspector<DB>,
+    inspector: &'a mut INSP,
     network_id: u64,
+    bytecode_cache: Option<&'a BytecodeCache>,
+    /// Frames of the running transaction, last one is executing.
+    frames: Vec<Frame>,
+    stage: Stage,
+    /// If set, execution is suspended when database fails to provide data, see [ResumableTransact].
+    resumable: bool,
+    /// Data the execution is suspended on, with the error database returned for it.
+    suspended: Option<(DataRequest, DB::Error)>,
     _phantomdata: PhantomData<GSPEC>,
 }
 
+/// Progress of the transaction, kept between suspensions of resumable execution.
+enum Stage {
+    /// Transaction is not yet validated and energy is not paid.
+    Validate,
+    /// Frames are running. `root` is the transaction call or create until its frame is created.
+    Execute {
+        caller: B176,
+        energy: Energy,
+        root: Option<InterpreterAction>,
+    },
+    /// Root frame finished, energy is refunded and rewarded.
+    Finalize {
+        caller: B176,
+        energy: Energy,
+        result: FrameResult,
+    },
+    Done,
+}
+
+/// Call or create frame on the frame stack of [EVMImpl].
+struct Frame {
+    interpreter: Interpreter,
+    /// Checkpoint taken when the frame was entered.
+    checkpoint: JournalCheckpoint,
+    kind: FrameKind,
+}
+
+enum FrameKind {
+    Call {
+        inputs: Box<CallInputs>,
+        return_memory_offset: Range<usize>,
+    },
+    Create {
+        inputs: Box<CreateInputs>,
+        created_address: B176,
+    },
+}
+
+/// Outcome of a finished frame, inserted into the interpreter of the parent frame.
+enum FrameResult {
+    Call {
+        ret: InstructionResult,
+        energy: Energy,
+        out: Bytes,
+        return_memory_offset: Range<usize>,
+        /// Error of the failed precompile call.
+        precompile_error: Option<PrecompileError>,
+    },
+    Create {
+        ret: InstructionResult,
+        address: Option<B176>,
+        energy: Energy,
+        out: Bytes,
+    },
+}
+
 pub trait Transact<DBError> {
     /// Do transaction.
     /// InstructionResult InstructionResult, Output for call or Address if we are creating contract, energy spend, energy refunded, State that needs to be applied.
     fn transact(&mut self) -> EVMResult<DBError>;
 }
 
-impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
-    for EVMImpl<'a, GSPEC, DB, INSPECT>
+impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool, INSP: Inspector<DB> + ?Sized>
+    Transact<DB::Error> for EVMImpl<'a, GSPEC, DB, INSPECT, INSP>
 {
     fn transact(&mut self) -> EVMResult<DB::Error> {
+        Ok(self
+            .run_transaction()?
+            .expect("only resumable execution is suspended"))
+    }
+}
+
+impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool, INSP: Inspector<DB> + ?Sized>
+    ResumableTransact<DB> for EVMImpl<'a, GSPEC, DB, INSPECT, INSP>
+{
+    fn resume(&mut self) -> Result<ResumeResult<DB::Error>, EVMError<DB::Error>> {
+        Ok(match self.run_transaction()? {
+            Some(result) => ResumeResult::Done(result),
+            None => {
+                let (request, error) = self
+                    .suspended
+                    .take()
+                    .expect("suspended execution has a request");
+                ResumeResult::NeedsData { request, error }
+            }
+        })
+    }
+
+    fn db(&mut self) -> &mut DB {
+        self.data.db
+    }
+}
+
+impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool, INSP: Inspector<DB> + ?Sized>
+    EVMImpl<'a, GSPEC, DB, INSPECT, INSP>
+{
+    pub fn new(
+        db: &'a mut DB,
+        env: &'a mut Env,
+        inspector: &'a mut INSP,
+        precompiles: Precompiles,
+        network_id: u64,
+        bytecode_cache: Option<&'a BytecodeCache>,
+    ) -> Self {
+        let journaled_state = if GSPEC::enabled(SpecId::SPURIOUS_DRAGON) {
+            JournaledState::new(precompiles.len())
+        } else {
+            JournaledState::new_legacy(precompiles.len())
+        };
+        Self {
+            data: EVMData {
+                env,
+                journaled_state,
+                db,
+                error: None,
+                precompile_error: None,
+            },
+            precompiles,
+            inspector,
+            network_id,
+            bytecode_cache,
+            frames: Vec::new(),
+            stage: Stage::Validate,
+            resumable: false,
+            suspended: None,
+            _phantomdata: PhantomData {},
+        }
+    }
+
+    /// Suspend execution instead of failing when database returns an error.
+    pub fn resumable(mut self) -> Self {
+        self.resumable = true;
+        self
+    }
+
+    /// Check that database can provide the account and, if `with_code`, its bytecode.
+    /// Returns false and records the request if execution has to be suspended.
+    fn probe_account(&mut self, address: B176, with_code: bool) -> bool {
+        if !self.resumable {
+            return true;
+        }
+        let (code_hash, has_code) = match self.data.journaled_state.state.get(&address) {
+            Some(account) => (account.info.code_hash, account.info.code.is_some()),
+            None => match self.data.db.basic(address) {
+                Ok(Some(info)) => (info.code_hash, info.code.is_some()),
+                Ok(None) => return true,
+                Err(error) => return self.suspend(DataRequest::Account(address), error),
+            },
+        };
+        if !with_code || has_code || code_hash == SHA3_EMPTY {
+            return true;
+        }
+        match self.data.db.code_by_hash(code_hash) {
+            Ok(_) => true,
+            Err(error) => self.suspend(DataRequest::Code(code_hash), error),
+        }
+    }
+
+    /// Check that database can provide the storage slot of the loaded account.
+    fn probe_storage(&mut self, address: B176, index: U256) -> bool {
+        if let Some(account) = self.data.journaled_state.state.get(&address) {
+            if account.storage_cleared || account.storage.contains_key(&index) {
+                return true;
+            }
+        }
+        match self.data.db.storage(address, index) {
+            Ok(_) => true,
+            Err(error) => self.suspend(DataRequest::Storage(address, index), error),
+        }
+    }
+
+    /// Check that database can provide data the next instruction of the interpreter loads.
+    fn probe_instruction(&mut self, interp: &Interpreter) -> bool {
+        let stack = &interp.stack;
+        // stack underflow is reported by the instruction itself.
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => match stack.peek(0) {
+                Ok(index) => self.probe_storage(interp.contract.address, index),
+                Err(_) => true,
+            },
+            opcode::BALANCE | opcode::SELFDESTRUCT => match stack.peek(0) {
+                Ok(address) => self.probe_account(to_address(address), false),
+                Err(_) => true,
+            },
+            opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH => {
+                match stack.peek(0) {
+                    Ok(address) => self.probe_account(to_address(address), true),
+                    Err(_) => true,
+                }
+            }
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                match stack.peek(1) {
+                    Ok(address) => self.probe_account(to_address(address), true),
+                    Err(_) => true,
+                }
+            }
+            opcode::BLOCKHASH => match stack.peek(0) {
+                Ok(number) => {
+                    // only last 256 blocks are loaded.
+                    let diff = self.data.env.block.number.checked_sub(number);
+                    if !matches!(diff, Some(diff) if diff != U256::ZERO && diff <= U256::from(256))
+                    {
+                        return true;
+                    }
+                    match self.data.db.block_hash(number) {
+                        Ok(_) => true,
+                        Err(error) => self.suspend(DataRequest::BlockHash(number), error),
+                    }
+                }
+                Err(_) => true,
+            },
+            _ => true,
+        }
+    }
+
+    /// Check that database can provide accounts needed to enter the call or create frame.
+    fn probe_action(&mut self, action: &InterpreterAction) -> bool {
+        if !self.resumable {
+            return true;
+        }
+        match action {
+            InterpreterAction::Call { inputs, .. } => {
+                self.probe_account(inputs.contract, true)
+                    && self.probe_account(inputs.context.address, false)
+                    && self.probe_account(inputs.transfer.source, false)
+                    && self.probe_account(inputs.transfer.target, false)
+            }
+            InterpreterAction::Create { inputs } => {
+                if !self.probe_account(inputs.caller, false) {
+                    return false;
+                }
+                // nonce overflow is handled when the frame is created.
+                let nonce = match self.data.journaled_state.state.get(&inputs.caller) {
+                    Some(account) => account.info.nonce,
+                    None => return true,
+                };
+                let created_address = self.created_address(inputs, nonce);
+                self.probe_account(created_address, true)
+            }
+        }
+    }
+
+    fn suspend(&mut self, request: DataRequest, error: DB::Error) -> bool {
+        self.suspended = Some((request, error));
+        false
+    }
+
+    /// Address of the contract created by `inputs` if caller has `nonce`.
+    fn created_address(&self, inputs: &CreateInputs, nonce: u64) -> B176 {
+        let network = Network::from(self.network_id);
+        match inputs.scheme {
+            CreateScheme::Create => create_address(inputs.caller, nonce, network),
+            CreateScheme::Create2 { salt } => {
+                create2_address(inputs.caller, sha3(&inputs.init_code), salt, network)
+            }
+        }
+    }
+
+    /// Run the transaction from its current stage. Returns `None` if execution is suspended.
+    fn run_transaction(&mut self) -> Result<Option<ResultAndState>, EVMError<DB::Error>> {
+        loop {
+            match core::mem::replace(&mut self.stage, Stage::Done) {
+                Stage::Validate => {
+                    if !self.probe_account(self.data.env.tx.caller, false) {
+                        self.stage = Stage::Validate;
+                        return Ok(None);
+                    }
+                    let (caller, energy, root) = self.validate()?;
+                    self.stage = match root {
+                        Ok(action) => Stage::Execute {
+                            caller,
+                            energy,
+                            root: Some(action),
+                        },
+                        Err(result) => Stage::Finalize {
+                            caller,
+                            energy,
+                            result,
+                        },
+                    };
+                }
+                Stage::Execute {
+                    caller,
+                    energy,
+                    mut root,
+                } => match self.run_frames(&mut root) {
+                    Some(result) => {
+                        self.stage = Stage::Finalize {
+                            caller,
+                            energy,
+                            result,
+                        }
+                    }
+                    None => {
+                        self.stage = Stage::Execute {
+                            caller,
+                            energy,
+                            root,
+                        };
+                        return Ok(None);
+                    }
+                },
+                Stage::Finalize {
+                    caller,
+                    energy,
+                    result,
+                } => {
+                    if !self.probe_account(self.data.env.block.coinbase, false) {
+                        self.stage = Stage::Finalize {
+                            caller,
+                            energy,
+                            result,
+                        };
+                        return Ok(None);
+                    }
+                    return self.finish(caller, energy, result).map(Some);
+                }
+                Stage::Done => panic!("Transaction is already finished"),
+            }
+        }
+    }
+
+    /// Validate transaction and pay for energy. Returns transaction call or create, or its result
+    /// if it can't be executed.
+    #[allow(clippy::type_complexity)]
+    fn validate(
+        &mut self,
+    ) -> Result<(B176, Energy, Result<InterpreterAction, FrameResult>), EVMError<DB::Error>> {
         let caller = self.data.env.tx.caller;
         let value = self.data.env.tx.value;
         let data = self.data.env.tx.data.clone();
@@ -154,9 +499,7 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
             energy.record_cost(energy_limit);
         }
 
-        // call inner handling of call/create
-        // TODO can probably be refactored to look nicer.
-        let (exit_reason, ret_energy, output) = match self.data.env.tx.transact_to {
+        let root = match self.data.env.tx.transact_to {
             TransactTo::Call(address) => {
                 if self.data.journaled_state.inc_nonce(caller).is_some() {
                     let context = CallContext {
@@ -166,7 +509,7 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
                         apparent_value: value,
                         scheme: CallScheme::Call,
                     };
-                    let mut call_input = CallInputs {
+                    let call_input = CallInputs {
                         contract: address,
                         transfer: Transfer {
                             source: caller,
@@ -178,29 +521,58 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
                         context,
                         is_static: false,
                     };
-                    let (exit, energy, bytes) =
-                        self.call_inner(&mut call_input, Network::from(self.network_id));
-                    (exit, energy, Output::Call(bytes))
+                    Ok(InterpreterAction::Call {
+                        inputs: Box::new(call_input),
+                        return_memory_offset: 0..0,
+                    })
                 } else {
-                    (
-                        InstructionResult::NonceOverflow,
+                    Err(FrameResult::Call {
+                        ret: InstructionResult::NonceOverflow,
                         energy,
-                        Output::Call(Bytes::new()),
-                    )
+                        out: Bytes::new(),
+                        return_memory_offset: 0..0,
+                        precompile_error: None,
+                    })
                 }
             }
             TransactTo::Create(scheme) => {
-                let mut create_input = CreateInputs {
+                let create_input = CreateInputs {
                     caller,
                     scheme,
                     value,
                     init_code: data,
                     energy_limit,
                 };
-                let (exit, address, ret_energy, bytes) = self.create_inner(&mut create_input);
-                (exit, ret_energy, Output::Create(bytes, address))
+                Ok(InterpreterAction::Create {
+                    inputs: Box::new(create_input),
+                })
             }
         };
+        Ok((caller, energy, root))
+    }
+
+    /// Refund unused energy, reward coinbase and build the transaction result.
+    fn finish(
+        &mut self,
+        caller: B176,
+        mut energy: Energy,
+        result: FrameResult,
+    ) -> EVMResult<DB::Error> {
+        let (exit_reason, ret_energy, output, precompile_error) = match result {
+            FrameResult::Call {
+                ret,
+                energy,
+                out,
+                precompile_error,
+                ..
+            } => (ret, energy, Output::Call(out), precompile_error),
+            FrameResult::Create {
+                ret,
+                address,
+                energy,
+                out,
+            } => (ret, energy, Output::Create(out, address), None),
+        };
 
         if crate::USE_ENERGY {
             match exit_reason {
@@ -239,41 +611,20 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> Transact<DB::Error>
             SuccessOrHalt::FatalExternalError => {
                 return Err(EVMError::Database(self.data.error.take().unwrap()))
             }
-            SuccessOrHalt::InternalContinue => {
+            SuccessOrHalt::PrecompileError => ExecutionResult::Halt {
+                // result can be set by an inspector without a precompile failing.
+                reason: Halt::PrecompileError(precompile_error.unwrap_or(PrecompileError::Other)),
+                energy_used,
+            },
+            SuccessOrHalt::InternalContinue
+            | SuccessOrHalt::InternalCallOrCreate
+            | SuccessOrHalt::InternalSuspend => {
                 panic!("Internal return flags should remain internal {exit_reason:?}")
             }
         };
 
         Ok(ResultAndState { result, state })
     }
-}
-
-impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB, INSPECT> {
-    pub fn new(
-        db: &'a mut DB,
-        env: &'a mut Env,
-        inspector: &'a mut dyn Inspector<DB>,
-        precompiles: Precompiles,
-        network_id: u64,
-    ) -> Self {
-        let journaled_state = if GSPEC::enabled(SpecId::SPURIOUS_DRAGON) {
-            JournaledState::new(precompiles.len())
-        } else {
-            JournaledState::new_legacy(precompiles.len())
-        };
-        Self {
-            data: EVMData {
-                env,
-                journaled_state,
-                db,
-                error: None,
-            },
-            precompiles,
-            inspector,
-            network_id,
-            _phantomdata: PhantomData {},
-        }
-    }
 
     #[allow(clippy::extra_unused_type_parameters)]
     fn finalize<SPEC: Spec>(
@@ -395,22 +746,150 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         }
     }
 
-    fn create_inner(
-        &mut self,
-        inputs: &mut CreateInputs,
-    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+    /// Run frames until the root one finishes, returns `None` if execution is suspended.
+    ///
+    /// Calls and creates requested by the interpreter suspend it and push a new frame on the
+    /// frame stack, when frame finishes its outcome is inserted into the parent interpreter that
+    /// is then resumed. Native stack usage does not depend on call depth.
+    fn run_frames(&mut self, root: &mut Option<InterpreterAction>) -> Option<FrameResult> {
+        if let Some(action) = root.take() {
+            if !self.probe_action(&action) {
+                *root = Some(action);
+                return None;
+            }
+            match self.make_frame(action) {
+                Ok(frame) => self.frames.push(frame),
+                Err(result) => return Some(result),
+            }
+        }
+
+        let mut outcome = None;
+        loop {
+            let mut frame = self.frames.pop().expect("frame stack is not empty");
+            let interpreter = &mut frame.interpreter;
+
+            let mut exit_reason = InstructionResult::Continue;
+            if let Some(result) = outcome.take() {
+                match result {
+                    FrameResult::Call {
+                        ret,
+                        energy,
+                        out,
+                        return_memory_offset,
+                        ..
+                    } => interpreter.insert_call_outcome(ret, energy, out, return_memory_offset),
+                    FrameResult::Create {
+                        ret,
+                        address,
+                        energy,
+                        out,
+                    } => interpreter.insert_create_outcome(ret, address, energy, out),
+                }
+                if INSPECT {
+                    // step end of the instruction that made the call.
+                    let (is_static, eval) = (interpreter.is_static, interpreter.instruction_result);
+                    exit_reason = inspector_result(self.inspector.step_end(
+                        interpreter,
+                        &mut self.data,
+                        is_static,
+                        eval,
+                    ));
+                }
+            }
+            if exit_reason == InstructionResult::Continue {
+                exit_reason = if INSPECT {
+                    interpreter.run_inspect::<Self, GSPEC>(self)
+                } else {
+                    interpreter.run::<Self, GSPEC>(self)
+                };
+            }
+
+            // instruction is not executed yet, it is run again on resume.
+            if exit_reason == InstructionResult::Suspend && self.suspended.is_some() {
+                self.frames.push(frame);
+                return None;
+            }
+            if exit_reason == InstructionResult::CallOrCreate {
+                if let Some(action) = interpreter.next_action.take() {
+                    if !self.probe_action(&action) {
+                        interpreter.next_action = Some(action);
+                        self.frames.push(frame);
+                        return None;
+                    }
+                    let next = self.make_frame(action);
+                    self.frames.push(frame);
+                    match next {
+                        Ok(frame) => self.frames.push(frame),
+                        Err(result) => outcome = Some(result),
+                    }
+                    continue;
+                }
+            }
+
+            let result = self.finish_frame(frame, exit_reason);
+            if self.frames.is_empty() {
+                return Some(result);
+            }
+            outcome = Some(result);
+        }
+    }
+
+    fn make_frame(&mut self, action: InterpreterAction) -> Result<Frame, FrameResult> {
+        match action {
+            InterpreterAction::Call {
+                inputs,
+                return_memory_offset,
+            } => self.make_call_frame(inputs, return_memory_offset),
+            InterpreterAction::Create { inputs } => self.make_create_frame(inputs),
+        }
+    }
+
+    /// Commit or revert the frame's subroutine and call the inspector end hook.
+    fn finish_frame(&mut self, frame: Frame, exit_reason: InstructionResult) -> FrameResult {
+        let Frame {
+            interpreter,
+            checkpoint,
+            kind,
+        } = frame;
+        match kind {
+            FrameKind::Call {
+                inputs,
+                return_memory_offset,
+            } => {
+                if matches!(exit_reason, return_ok!()) {
+                    self.data.journaled_state.checkpoint_commit();
+                } else {
+                    self.data.journaled_state.checkpoint_revert(checkpoint);
+                }
+                self.call_end(
+                    &inputs,
+                    return_memory_offset,
+                    exit_reason,
+                    interpreter.energy,
+                    interpreter.return_value(),
+                )
+            }
+            FrameKind::Create {
+                inputs,
+                created_address,
+            } => self.finish_create(
+                &inputs,
+                created_address,
+                checkpoint,
+                interpreter,
+                exit_reason,
+            ),
+        }
+    }
+
+    /// Create frame that executes init code, or the result if create failed before that.
+    fn make_create_frame(&mut self, mut inputs: Box<CreateInputs>) -> Result<Frame, FrameResult> {
         // Call inspector
         if INSPECT {
-            let (ret, address, energy, out) = self.inspector.create(&mut self.data, inputs);
+            let (ret, address, energy, out) = self.inspector.create(&mut self.data, &mut inputs);
+            let ret = inspector_result(ret);
             if ret != InstructionResult::Continue {
-                return self.inspector.create_end(
-                    &mut self.data,
-                    inputs,
-                    ret,
-                    address,
-                    energy,
-                    out,
-                );
+                return Err(self.create_end(&inputs, ret, address, energy, out));
             }
         }
 
@@ -419,34 +898,34 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
 
         // Check depth of calls
         if self.data.journaled_state.depth() > CALL_STACK_LIMIT {
-            return self.create_end(
-                inputs,
+            return Err(self.create_end(
+                &inputs,
                 InstructionResult::CallTooDeep,
                 None,
                 energy,
                 Bytes::new(),
-            );
+            ));
         }
         // Check balance of caller and value. Do this before increasing nonce
         match self.balance(inputs.caller) {
             Some(i) if i.0 < inputs.value => {
-                return self.create_end(
-                    inputs,
+                return Err(self.create_end(
+                    &inputs,
                     InstructionResult::OutOfFund,
                     None,
                     energy,
                     Bytes::new(),
-                )
+                ))
             }
             Some(_) => (),
             _ => {
-                return self.create_end(
-                    inputs,
+                return Err(self.create_end(
+                    &inputs,
                     InstructionResult::FatalExternalError,
                     None,
                     energy,
                     Bytes::new(),
-                )
+                ))
             }
         }
 
@@ -455,28 +934,17 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         if let Some(nonce) = self.data.journaled_state.inc_nonce(inputs.caller) {
             old_nonce = nonce - 1;
         } else {
-            return self.create_end(
-                inputs,
+            return Err(self.create_end(
+                &inputs,
                 InstructionResult::Return,
                 None,
                 energy,
                 Bytes::new(),
-            );
+            ));
         }
 
         // Create address
-        let code_hash = sha3(&inputs.init_code);
-        let created_address = match inputs.scheme {
-            CreateScheme::Create => {
-                create_address(inputs.caller, old_nonce, Network::from(self.network_id))
-            }
-            CreateScheme::Create2 { salt } => create2_address(
-                inputs.caller,
-                code_hash,
-                salt,
-                Network::from(self.network_id),
-            ),
-        };
+        let created_address = self.created_address(&inputs, old_nonce);
         let ret = Some(created_address);
 
         // Load account so that it will be hot
@@ -493,23 +961,23 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         ) {
             Ok(false) => {
                 self.data.journaled_state.checkpoint_revert(checkpoint);
-                return self.create_end(
-                    inputs,
+                return Err(self.create_end(
+                    &inputs,
                     InstructionResult::CreateCollision,
                     ret,
                     energy,
                     Bytes::new(),
-                );
+                ));
             }
             Err(err) => {
                 self.data.error = Some(err);
-                return self.create_end(
-                    inputs,
+                return Err(self.create_end(
+                    &inputs,
                     InstructionResult::FatalExternalError,
                     ret,
                     energy,
                     Bytes::new(),
-                );
+                ));
             }
             Ok(true) => (),
         }
@@ -522,7 +990,7 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             self.data.db,
         ) {
             self.data.journaled_state.checkpoint_revert(checkpoint);
-            return self.create_end(inputs, e, ret, energy, Bytes::new());
+            return Err(self.create_end(&inputs, e, ret, energy, Bytes::new()));
         }
 
         // EIP-161: State trie clearing (invariant-preserving alternative)
@@ -535,16 +1003,16 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         {
             // overflow
             self.data.journaled_state.checkpoint_revert(checkpoint);
-            return self.create_end(
-                inputs,
+            return Err(self.create_end(
+                &inputs,
                 InstructionResult::Return,
                 None,
                 energy,
                 Bytes::new(),
-            );
+            ));
         }
 
-        // Create new interpreter and execute initcode
+        // Create new interpreter that will execute initcode
         let contract = Contract::new(
             Bytes::new(),
             Bytecode::new_raw(inputs.init_code.clone()),
@@ -568,13 +1036,28 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             self.inspector
                 .initialize_interp(&mut interpreter, &mut self.data, false);
         }
-        let exit_reason = if INSPECT {
-            interpreter.run_inspect::<Self, GSPEC>(self)
-        } else {
-            interpreter.run::<Self, GSPEC>(self)
-        };
-        // Host error if present on execution\
-        let (ret, address, energy, out) = match exit_reason {
+        Ok(Frame {
+            interpreter,
+            checkpoint,
+            kind: FrameKind::Create {
+                inputs,
+                created_address,
+            },
+        })
+    }
+
+    /// Deposit code of the created contract if init code succeeded.
+    fn finish_create(
+        &mut self,
+        inputs: &CreateInputs,
+        created_address: B176,
+        checkpoint: JournalCheckpoint,
+        mut interpreter: Interpreter,
+        exit_reason: InstructionResult,
+    ) -> FrameResult {
+        let ret = Some(created_address);
+        // Host error if present on execution
+        match exit_reason {
             return_ok!() => {
                 // if ok, check contract creation limit and calculate energy deduction on output len.
                 let mut bytes = interpreter.return_value();
@@ -632,20 +1115,20 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
                 self.data
                     .journaled_state
                     .set_code(created_address, bytecode);
-                (InstructionResult::Return, ret, interpreter.energy, bytes)
-            }
-            _ => {
-                self.data.journaled_state.checkpoint_revert(checkpoint);
-                (
-                    exit_reason,
+                self.create_end(
+                    inputs,
+                    InstructionResult::Return,
                     ret,
                     interpreter.energy,
-                    interpreter.return_value(),
+                    bytes,
                 )
             }
-        };
-
-        self.create_end(inputs, ret, address, energy, out)
+            _ => {
+                self.data.journaled_state.checkpoint_revert(checkpoint);
+                let out = interpreter.return_value();
+                self.create_end(inputs, exit_reason, ret, interpreter.energy, out)
+            }
+        }
     }
 
     fn create_end(
@@ -655,34 +1138,35 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         address: Option<B176>,
         energy: Energy,
         out: Bytes,
-    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
-        if INSPECT {
+    ) -> FrameResult {
+        let (ret, address, energy, out) = if INSPECT {
             self.inspector
                 .create_end(&mut self.data, inputs, ret, address, energy, out)
         } else {
             (ret, address, energy, out)
+        };
+        FrameResult::Create {
+            ret,
+            address,
+            energy,
+            out,
         }
     }
 
-    fn call_inner(
+    /// Create frame that executes the called contract, or the result if call finished without
+    /// running the interpreter (precompile, failed transfer, inspector override).
+    fn make_call_frame(
         &mut self,
-        inputs: &mut CallInputs,
-        network: Network,
-    ) -> (InstructionResult, Energy, Bytes) {
+        mut inputs: Box<CallInputs>,
+        return_memory_offset: Range<usize>,
+    ) -> Result<Frame, FrameResult> {
         // Call the inspector
         if INSPECT {
-            let (ret, energy, out) = self
-                .inspector
-                .call(&mut self.data, inputs, inputs.is_static);
+            let is_static = inputs.is_static;
+            let (ret, energy, out) = self.inspector.call(&mut self.data, &mut inputs, is_static);
+            let ret = inspector_result(ret);
             if ret != InstructionResult::Continue {
-                return self.inspector.call_end(
-                    &mut self.data,
-                    inputs,
-                    energy,
-                    ret,
-                    out,
-                    inputs.is_static,
-                );
+                return Err(self.call_end(&inputs, return_memory_offset, ret, energy, out));
             }
         }
 
@@ -691,24 +1175,29 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
         let bytecode = if let Some((bytecode, _)) = self.code(inputs.contract) {
             bytecode
         } else {
-            return (InstructionResult::FatalExternalError, energy, Bytes::new());
+            return Err(FrameResult::Call {
+                ret: InstructionResult::FatalExternalError,
+                energy,
+                out: Bytes::new(),
+                return_memory_offset,
+                precompile_error: None,
+            });
+        };
+        // Reuse analysis done in previous transactions.
+        let bytecode = match self.bytecode_cache {
+            Some(cache) => cache.get_or_analyse(bytecode),
+            None => bytecode,
         };
 
         // Check depth
         if self.data.journaled_state.depth() > CALL_STACK_LIMIT {
-            let (ret, energy, out) = (InstructionResult::CallTooDeep, energy, Bytes::new());
-            if INSPECT {
-                return self.inspector.call_end(
-                    &mut self.data,
-                    inputs,
-                    energy,
-                    ret,
-                    out,
-                    inputs.is_static,
-                );
-            } else {
-                return (ret, energy, out);
-            }
+            return Err(self.call_end(
+                &inputs,
+                return_memory_offset,
+                InstructionResult::CallTooDeep,
+                energy,
+                Bytes::new(),
+            ));
         }
 
         // Create subroutine checkpoint
@@ -728,99 +1217,104 @@ impl<'a, GSPEC: Spec, DB: Database, const INSPECT: bool> EVMImpl<'a, GSPEC, DB,
             self.data.db,
         ) {
             self.data.journaled_state.checkpoint_revert(checkpoint);
-            let (ret, energy, out) = (e, energy, Bytes::new());
-            if INSPECT {
-                return self.inspector.call_end(
-                    &mut self.data,
-                    inputs,
-                    energy,
-                    ret,
-                    out,
-                    inputs.is_static,
-                );
-            } else {
-                return (ret, energy, out);
-            }
+            return Err(self.call_end(&inputs, return_memory_offset, e, energy, Bytes::new()));
         }
 
         // Call precompiles
-        let (ret, energy, out) = if let Some(precompile) = self.precompiles.get(&inputs.contract) {
+        if let Some(precompile) = self.precompiles.get(&inputs.contract) {
+            let network = Network::from(self.network_id);
+            self.data.precompile_error = None;
             let out = match precompile {
                 Precompile::Standard(fun) => {
                     fun(inputs.input.as_ref(), inputs.energy_limit, network)
                 }
                 Precompile::Custom(fun) => fun(inputs.input.as_ref(), inputs.energy_limit, network),
             };
-            match out {
+            let (ret, out) = match out {
                 Ok((energy_used, data)) => {
                     if !crate::USE_ENERGY || energy.record_cost(energy_used) {
                         self.data.journaled_state.checkpoint_commit();
-                        (InstructionResult::Return, energy, Bytes::from(data))
+                        (InstructionResult::Return, Bytes::from(data))
                     } else {
                         self.data.journaled_state.checkpoint_revert(checkpoint);
-                        (InstructionResult::PrecompileOOG, energy, Bytes::new())
+                        (InstructionResult::PrecompileOOG, Bytes::new())
                     }
                 }
                 Err(e) => {
                     let ret = if let precompile::Error::OutOfEnergy = e {
                         InstructionResult::PrecompileOOG
                     } else {
+                        self.data.precompile_error = Some(e);
                         InstructionResult::PrecompileError
                     };
                     self.data.journaled_state.checkpoint_revert(checkpoint);
-                    (ret, energy, Bytes::new())
+                    (ret, Bytes::new())
                 }
-            }
-        } else {
-            // Create interpreter and execute subcall
-            let contract =
-                Contract::new_with_context(inputs.input.clone(), bytecode, &inputs.context);
-
-            #[cfg(feature = "memory_limit")]
-            let mut interpreter = Interpreter::new_with_memory_limit(
-                contract,
-                energy.limit(),
-                inputs.is_static,
-                self.data.env.cfg.memory_limit,
-            );
-
-            #[cfg(not(feature = "memory_limit"))]
-            let mut interpreter = Interpreter::new(contract, energy.limit(), inputs.is_static);
-
-            if INSPECT {
-                // create is always no static call.
-                self.inspector
-                    .initialize_interp(&mut interpreter, &mut self.data, false);
-            }
-            let exit_reason = if INSPECT {
-                interpreter.run_inspect::<Self, GSPEC>(self)
-            } else {
-                interpreter.run::<Self, GSPEC>(self)
             };
+            return Err(self.call_end(&inputs, return_memory_offset, ret, energy, out));
+        }
 
-            if matches!(exit_reason, return_ok!()) {
-                self.data.journaled_state.checkpoint_commit();
-            } else {
-                self.data.journaled_state.checkpoint_revert(checkpoint);
-            }
+        // Create interpreter that will execute the subcall
+        let contract = Contract::new_with_context(inputs.input.clone(), bytecode, &inputs.context);
 
-            (exit_reason, interpreter.energy, interpreter.return_value())
-        };
+        #[cfg(feature = "memory_limit")]
+        let mut interpreter = Interpreter::new_with_memory_limit(
+            contract,
+            energy.limit(),
+            inputs.is_static,
+            self.data.env.cfg.memory_limit,
+        );
+
+        #[cfg(not(feature = "memory_limit"))]
+        let mut interpreter = Interpreter::new(contract, energy.limit(), inputs.is_static);
 
         if INSPECT {
+            // create is always no static call.
+            self.inspector
+                .initialize_interp(&mut interpreter, &mut self.data, false);
+        }
+        Ok(Frame {
+            interpreter,
+            checkpoint,
+            kind: FrameKind::Call {
+                inputs,
+                return_memory_offset,
+            },
+        })
+    }
+
+    fn call_end(
+        &mut self,
+        inputs: &CallInputs,
+        return_memory_offset: Range<usize>,
+        ret: InstructionResult,
+        energy: Energy,
+        out: Bytes,
+    ) -> FrameResult {
+        let (ret, energy, out) = if INSPECT {
             self.inspector
                 .call_end(&mut self.data, inputs, energy, ret, out, inputs.is_static)
         } else {
             (ret, energy, out)
+        };
+        FrameResult::Call {
+            ret,
+            energy,
+            out,
+            return_memory_offset,
+            precompile_error: self.data.precompile_error.take(),
         }
     }
 }
 
-impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
-    for EVMImpl<'a, GSPEC, DB, INSPECT>
+impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool, INSP: Inspector<DB> + ?Sized> Host
+    for EVMImpl<'a, GSPEC, DB, INSPECT, INSP>
 {
     fn step(&mut self, interp: &mut Interpreter, is_static: bool) -> InstructionResult {
-        self.inspector.step(interp, &mut self.data, is_static)
+        if self.resumable && !self.probe_instruction(interp) {
+            return InstructionResult::Suspend;
+        }
+        inspector_result(self.inspector.step(interp, &mut self.data, is_static))
     }
 
     fn step_end(
@@ -829,8 +1323,10 @@ impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
         is_static: bool,
         ret: InstructionResult,
     ) -> InstructionResult {
-        self.inspector
-            .step_end(interp, &mut self.data, is_static, ret)
+        inspector_result(
+            self.inspector
+                .step_end(interp, &mut self.data, is_static, ret),
+        )
     }
 
     fn env(&mut self) -> &mut Env {
@@ -943,15 +1439,267 @@ impl<'a, GSPEC: Spec, DB: Database + 'a, const INSPECT: bool> Host
             .map_err(|e| self.data.error = Some(e))
             .ok()
     }
+}
 
-    fn create(
-        &mut self,
-        inputs: &mut CreateInputs,
-    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
-        self.create_inner(inputs)
+/// Address is stored in the lower 22 bytes of the stack item.
+fn to_address(item: U256) -> B176 {
+    B176(
+        item.to_be_bytes::<{ U256::BYTES }>()[10..]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+/// Internal results returned by the inspector are ignored, execution is suspended only by the
+/// interpreter and the frame loop would not know how to resume it.
+fn inspector_result(ret: InstructionResult) -> InstructionResult {
+    match ret {
+        InstructionResult::CallOrCreate | InstructionResult::Suspend => InstructionResult::Continue,
+        ret => ret,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::BenchmarkDB;
+    use crate::interpreter::{
+        opcode, CallInputs, Energy, InstructionResult, Interpreter, CALL_STACK_LIMIT,
+    };
+    use crate::primitives::{
+        hex_literal::hex, Bytecode, Bytes, ExecutionResult, Halt, PrecompileError, TransactTo, B176,
+    };
+    use crate::{Database, EVMData, Inspector};
+
+    #[derive(Default)]
+    struct DepthInspector {
+        max_depth: u64,
+    }
+
+    impl<DB: Database> Inspector<DB> for DepthInspector {
+        fn call(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            _inputs: &mut CallInputs,
+            _is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            self.max_depth = self.max_depth.max(data.journaled_state.depth());
+            (InstructionResult::Continue, Energy::new(0), Bytes::new())
+        }
+    }
+
+    #[derive(Default)]
+    struct PrecompileErrorInspector {
+        error: Option<PrecompileError>,
+    }
+
+    impl<DB: Database> Inspector<DB> for PrecompileErrorInspector {
+        fn call_end(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            _inputs: &CallInputs,
+            remaining_energy: Energy,
+            ret: InstructionResult,
+            out: Bytes,
+            _is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            if ret == InstructionResult::PrecompileError {
+                self.error = data.precompile_error;
+            }
+            (ret, remaining_energy, out)
+        }
+    }
+
+    /// Ends calls to `target` with [InstructionResult::PrecompileError].
+    struct ForcePrecompileErrorInspector {
+        target: B176,
+    }
+
+    impl<DB: Database> Inspector<DB> for ForcePrecompileErrorInspector {
+        fn call_end(
+            &mut self,
+            _data: &mut EVMData<'_, DB>,
+            inputs: &CallInputs,
+            remaining_energy: Energy,
+            ret: InstructionResult,
+            out: Bytes,
+            _is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            if inputs.contract == self.target {
+                return (InstructionResult::PrecompileError, remaining_energy, out);
+            }
+            (ret, remaining_energy, out)
+        }
+    }
+
+    #[test]
+    fn test_call_depth_limit_on_small_stack() {
+        // contract that calls itself with all available energy.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::ADDRESS,
+            opcode::ENERGY,
+            opcode::CALL,
+            opcode::STOP,
+        ]);
+
+        // frames are not kept on native stack so depth limit is reachable with a small one.
+        let max_depth = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let mut evm = crate::new();
+                evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+                evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+                evm.env.tx.transact_to =
+                    TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+                evm.env.tx.energy_limit = u64::MAX / 2;
+
+                let mut inspector = DepthInspector::default();
+                let result = evm.inspect(&mut inspector).unwrap().result;
+                assert!(result.is_success());
+                inspector.max_depth
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(max_depth, CALL_STACK_LIMIT + 1);
+    }
+
+    #[test]
+    fn test_precompile_error_halt() {
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new()));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        // blake2 `F` precompile with empty input.
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000009")));
+        evm.env.tx.energy_limit = 100_000;
+
+        let mut inspector = PrecompileErrorInspector::default();
+        let result = evm.inspect(&mut inspector).unwrap().result;
+        assert_eq!(inspector.error, Some(PrecompileError::Blake2WrongLength));
+        let ExecutionResult::Halt { reason, .. } = result else {
+            panic!("expected halt, got {result:?}");
+        };
+        assert_eq!(
+            reason,
+            Halt::PrecompileError(PrecompileError::Blake2WrongLength)
+        );
+        assert_eq!(
+            reason.to_string(),
+            "precompile error: wrong input length for blake2"
+        );
+    }
+
+    #[test]
+    fn test_precompile_error_forced_by_inspector() {
+        // contract calls blake2 `F` precompile with empty input and continues after it failed.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x09,
+            opcode::ENERGY,
+            opcode::CALL,
+            opcode::STOP,
+        ]);
+        let target = B176(hex!("00000000000000000000000000000000000000000000"));
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to = TransactTo::Call(target);
+        evm.env.tx.energy_limit = 100_000;
+
+        let mut inspector = ForcePrecompileErrorInspector { target };
+        let result = evm.inspect(&mut inspector).unwrap().result;
+        // error of the nested blake2 call is not reported for the transaction.
+        let ExecutionResult::Halt { reason, .. } = result else {
+            panic!("expected halt, got {result:?}");
+        };
+        assert_eq!(reason, Halt::PrecompileError(PrecompileError::Other));
+    }
+
+    /// Returns internal results from every hook that can stop execution.
+    struct InternalResultInspector;
+
+    impl<DB: Database> Inspector<DB> for InternalResultInspector {
+        fn step(
+            &mut self,
+            _interp: &mut Interpreter,
+            _data: &mut EVMData<'_, DB>,
+            _is_static: bool,
+        ) -> InstructionResult {
+            InstructionResult::CallOrCreate
+        }
+
+        fn step_end(
+            &mut self,
+            _interp: &mut Interpreter,
+            _data: &mut EVMData<'_, DB>,
+            _is_static: bool,
+            _eval: InstructionResult,
+        ) -> InstructionResult {
+            InstructionResult::Suspend
+        }
+
+        fn call(
+            &mut self,
+            _data: &mut EVMData<'_, DB>,
+            _inputs: &mut CallInputs,
+            _is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            (InstructionResult::Suspend, Energy::new(0), Bytes::new())
+        }
     }
 
-    fn call(&mut self, inputs: &mut CallInputs) -> (InstructionResult, Energy, Bytes) {
-        self.call_inner(inputs, Network::from(self.network_id))
+    #[test]
+    fn test_inspector_internal_results_are_ignored() {
+        // contract that calls identity and returns its output.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x4,
+            opcode::ENERGY,
+            opcode::CALL,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x0,
+            opcode::RETURN,
+        ]);
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+        evm.env.tx.energy_limit = 100_000;
+
+        let result = evm.inspect(&mut InternalResultInspector).unwrap().result;
+        assert!(result.is_success(), "{result:?}");
     }
 }
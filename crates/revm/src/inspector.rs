@@ -4,18 +4,25 @@ use crate::primitives::{db::Database, Bytes, B176, B256};
 
 use auto_impl::auto_impl;
 
+pub mod access;
 #[cfg(feature = "std")]
 pub mod customprinter;
 pub mod energy;
+#[cfg(feature = "std")]
+pub mod limits;
 pub mod noop;
+mod stack;
 #[cfg(feature = "serde")]
 pub mod tracer_eip3155;
 
 /// All Inspectors implementations that revm has.
 pub mod inspectors {
+    pub use super::access::{AccessInspector, AccessSet, ReadWriteSet};
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
     pub use super::energy::EnergyInspector;
+    #[cfg(feature = "std")]
+    pub use super::limits::{ExecutionLimits, LimitsInspector};
     pub use super::noop::NoOpInspector;
     #[cfg(feature = "serde")]
     pub use super::tracer_eip3155::TracerEip3155;
@@ -66,6 +73,7 @@ pub trait Inspector<DB: Database> {
     /// Called after `step` when the instruction has been executed.
     ///
     /// InstructionResulting anything other than [InstructionResult::Continue] alters the execution of the interpreter.
+    /// Internal results [InstructionResult::CallOrCreate] and [InstructionResult::Suspend] are ignored.
     fn step_end(
         &mut self,
         _interp: &mut Interpreter,
@@ -0,0 +1,370 @@
+//! Inspector that records accounts and storage slots read and written by a transaction.
+//!
+//! Result can be used to build access lists or to detect conflicts between transactions
+//! that are executed in parallel.
+use crate::interpreter::{
+    opcode, CallInputs, CreateInputs, Energy, InstructionResult, Interpreter,
+};
+use crate::primitives::{Bytes, B176, U256};
+use crate::{Database, EVMData, Inspector};
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// Accessed state, split by the kind of account data that was touched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessSet {
+    /// Accounts that were loaded, or had their nonce changed when written.
+    pub accounts: BTreeSet<B176>,
+    /// Accounts whose balance was accessed.
+    pub balances: BTreeSet<B176>,
+    /// Accounts whose code (or code hash) was accessed.
+    pub code: BTreeSet<B176>,
+    /// Storage slots accessed per account.
+    pub storage: BTreeMap<B176, BTreeSet<U256>>,
+}
+
+impl AccessSet {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+            && self.balances.is_empty()
+            && self.code.is_empty()
+            && self.storage.is_empty()
+    }
+
+    /// All accounts present in the set, regardless of the kind of access.
+    pub fn addresses(&self) -> BTreeSet<B176> {
+        self.accounts
+            .iter()
+            .chain(self.balances.iter())
+            .chain(self.code.iter())
+            .chain(self.storage.keys())
+            .copied()
+            .collect()
+    }
+
+    fn insert_slot(&mut self, address: B176, index: U256) {
+        self.storage.entry(address).or_default().insert(index);
+    }
+}
+
+/// Reads and writes of a transaction. Writes are recorded even if the frame
+/// that made them reverted, as the set is meant to be a conservative approximation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadWriteSet {
+    pub reads: AccessSet,
+    pub writes: AccessSet,
+}
+
+/// Records every account load, `SLOAD`, `SSTORE`, balance transfer and code load.
+///
+/// Transaction level accesses are included: caller nonce and balance (energy payment) and
+/// coinbase balance (reward) are recorded as writes.
+#[derive(Clone, Debug, Default)]
+pub struct AccessInspector {
+    access: ReadWriteSet,
+}
+
+impl AccessInspector {
+    pub fn access(&self) -> &ReadWriteSet {
+        &self.access
+    }
+
+    pub fn into_access(self) -> ReadWriteSet {
+        self.access
+    }
+
+    fn record_transaction<DB: Database>(&mut self, data: &EVMData<'_, DB>) {
+        let writes = &mut self.access.writes;
+        writes.accounts.insert(data.env.tx.caller);
+        writes.balances.insert(data.env.tx.caller);
+        writes.balances.insert(data.env.block.coinbase);
+    }
+
+    fn record_transfer(&mut self, source: B176, target: B176, value: U256) {
+        let (reads, writes) = (&mut self.access.reads, &mut self.access.writes);
+        reads.balances.insert(source);
+        if value != U256::ZERO {
+            writes.balances.insert(source);
+            writes.balances.insert(target);
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for AccessInspector {
+    fn step(
+        &mut self,
+        interp: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+    ) -> InstructionResult {
+        let address = interp.contract.address;
+        let reads = &mut self.access.reads;
+        // stack underflow is reported by the instruction itself.
+        match interp.current_opcode() {
+            opcode::SLOAD => {
+                if let Ok(index) = interp.stack.peek(0) {
+                    reads.insert_slot(address, index);
+                }
+            }
+            opcode::SSTORE => {
+                if let Ok(index) = interp.stack.peek(0) {
+                    // original value is loaded to calculate the energy cost.
+                    reads.insert_slot(address, index);
+                    self.access.writes.insert_slot(address, index);
+                }
+            }
+            opcode::BALANCE => {
+                if let Ok(target) = interp.stack.peek(0) {
+                    reads.balances.insert(to_address(target));
+                }
+            }
+            opcode::SELFBALANCE => {
+                reads.balances.insert(address);
+            }
+            opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH => {
+                if let Ok(target) = interp.stack.peek(0) {
+                    reads.code.insert(to_address(target));
+                }
+            }
+            _ => (),
+        }
+        InstructionResult::Continue
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        _is_static: bool,
+    ) -> (InstructionResult, Energy, Bytes) {
+        if data.journaled_state.depth() == 0 {
+            self.record_transaction(data);
+        }
+        let reads = &mut self.access.reads;
+        reads.accounts.insert(inputs.context.address);
+        reads.accounts.insert(inputs.contract);
+        reads.code.insert(inputs.contract);
+        let transfer = &inputs.transfer;
+        self.record_transfer(transfer.source, transfer.target, transfer.value);
+        (InstructionResult::Continue, Energy::new(0), Bytes::new())
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        if data.journaled_state.depth() == 0 {
+            self.record_transaction(data);
+        }
+        self.access.reads.accounts.insert(inputs.caller);
+        // nonce of the creator is incremented.
+        self.access.writes.accounts.insert(inputs.caller);
+        self.access.reads.balances.insert(inputs.caller);
+        if inputs.value != U256::ZERO {
+            self.access.writes.balances.insert(inputs.caller);
+        }
+        (
+            InstructionResult::Continue,
+            None,
+            Energy::new(0),
+            Bytes::default(),
+        )
+    }
+
+    fn create_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B176>,
+        remaining_energy: Energy,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        if let Some(created) = address {
+            let writes = &mut self.access.writes;
+            writes.accounts.insert(created);
+            writes.code.insert(created);
+            if inputs.value != U256::ZERO {
+                writes.balances.insert(created);
+            }
+        }
+        (ret, address, remaining_energy, out)
+    }
+
+    fn selfdestruct(&mut self, contract: B176, target: B176) {
+        let writes = &mut self.access.writes;
+        writes.accounts.insert(contract);
+        writes.balances.insert(contract);
+        writes.balances.insert(target);
+    }
+}
+
+/// Address is stored in the lower 22 bytes of the stack item.
+fn to_address(item: U256) -> B176 {
+    B176(
+        item.to_be_bytes::<{ U256::BYTES }>()[10..]
+            .try_into()
+            .unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessInspector, ReadWriteSet};
+    use crate::db::BenchmarkDB;
+    use crate::interpreter::opcode;
+    use crate::primitives::{hex_literal::hex, Bytecode, Bytes, TransactTo, B176, U256};
+    use alloc::vec::Vec;
+
+    const CONTRACT: B176 = B176(hex!("00000000000000000000000000000000000000000000"));
+    const TARGET: B176 = B176(hex!("00000000000000000000000000000000000000001234"));
+
+    /// Call contract at zero address, the only account of the database, with `code`.
+    fn inspect(code: Vec<u8>) -> ReadWriteSet {
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to = TransactTo::Call(CONTRACT);
+        evm.env.tx.energy_limit = 100_000;
+        // coinbase balance is written, keep it apart from the accessed accounts.
+        evm.env.block.coinbase = B176(hex!("20000000000000000000000000000000000000000000"));
+
+        let mut inspector = AccessInspector::default();
+        evm.inspect(&mut inspector).unwrap();
+        inspector.into_access()
+    }
+
+    /// Code that calls `TARGET` with `value` and stops.
+    fn call_code(value: u8) -> Vec<u8> {
+        let mut code = [opcode::PUSH1, 0].repeat(4);
+        code.extend([opcode::PUSH1, value, opcode::PUSH2, 0x12, 0x34]);
+        code.extend([opcode::PUSH2, 0xff, 0xff, opcode::CALL, opcode::STOP]);
+        code
+    }
+
+    #[test]
+    fn test_storage_access() {
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x2,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x7,
+            opcode::BALANCE,
+            opcode::STOP,
+        ]);
+        let contract = B176(hex!("00000000000000000000000000000000000000000000"));
+        let caller = B176(hex!("10000000000000000000000000000000000000000000"));
+
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        evm.env.tx.caller = caller;
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+        evm.env.tx.energy_limit = 100_000;
+
+        let mut inspector = AccessInspector::default();
+        evm.inspect(&mut inspector).unwrap();
+        let access = inspector.into_access();
+
+        let slots = |slots: &[u64]| slots.iter().map(|&slot| U256::from(slot)).collect();
+        assert_eq!(access.reads.storage[&contract], slots(&[1, 2]));
+        assert_eq!(access.writes.storage[&contract], slots(&[2]));
+        assert!(access.reads.code.contains(&contract));
+        assert!(access
+            .reads
+            .balances
+            .contains(&B176(hex!("00000000000000000000000000000000000000000007"))));
+        assert!(access.writes.accounts.contains(&caller));
+        assert!(access.writes.balances.contains(&caller));
+    }
+
+    #[test]
+    fn test_call_transfer() {
+        let access = inspect(call_code(5));
+        assert!(access.reads.accounts.contains(&TARGET));
+        assert!(access.reads.code.contains(&TARGET));
+        assert!(access.reads.balances.contains(&CONTRACT));
+        assert!(access.writes.balances.contains(&CONTRACT));
+        assert!(access.writes.balances.contains(&TARGET));
+
+        // balance of the source is read, but nothing is written without value.
+        let access = inspect(call_code(0));
+        assert!(access.reads.balances.contains(&CONTRACT));
+        assert!(!access.writes.balances.contains(&CONTRACT));
+        assert!(!access.writes.balances.contains(&TARGET));
+    }
+
+    #[test]
+    fn test_create() {
+        let access = inspect(vec![
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            1,
+            opcode::CREATE,
+            opcode::STOP,
+        ]);
+        assert_eq!(access.writes.code.len(), 1);
+        let created = *access.writes.code.first().unwrap();
+        assert!(access.reads.accounts.contains(&CONTRACT));
+        assert!(access.writes.accounts.contains(&CONTRACT));
+        assert!(access.writes.accounts.contains(&created));
+        assert!(access.writes.balances.contains(&CONTRACT));
+        assert!(access.writes.balances.contains(&created));
+    }
+
+    #[test]
+    fn test_selfdestruct() {
+        let access = inspect(vec![opcode::PUSH2, 0x12, 0x34, opcode::SELFDESTRUCT]);
+        assert!(access.writes.accounts.contains(&CONTRACT));
+        assert!(access.writes.balances.contains(&CONTRACT));
+        assert!(access.writes.balances.contains(&TARGET));
+    }
+
+    #[test]
+    fn test_code_access() {
+        let access = inspect(vec![
+            opcode::PUSH1,
+            1,
+            opcode::EXTCODESIZE,
+            opcode::POP,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            2,
+            opcode::EXTCODECOPY,
+            opcode::PUSH1,
+            3,
+            opcode::EXTCODEHASH,
+            opcode::STOP,
+        ]);
+        let reads = [1, 2, 3].map(|byte| {
+            let mut address = [0; 22];
+            address[21] = byte;
+            B176(address)
+        });
+        assert!(reads
+            .iter()
+            .all(|address| access.reads.code.contains(address)));
+        assert!(access.writes.code.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let access = inspect(call_code(5));
+        let json = serde_json::to_string(&access).unwrap();
+        assert_eq!(serde_json::from_str::<ReadWriteSet>(&json).unwrap(), access);
+    }
+}
@@ -0,0 +1,226 @@
+//! Inspector that bounds execution by instruction count, wall-clock time and memory.
+//!
+//! Energy is not a sufficient bound for simulations where caller sets a huge energy limit,
+//! this inspector halts execution with [InstructionResult::StepLimitExceeded],
+//! [InstructionResult::TimeLimitExceeded] or [InstructionResult::MemoryBudgetExceeded], that
+//! are reported as [crate::primitives::Halt::ExecutionLimit] in the execution result.
+use crate::interpreter::{CallInputs, CreateInputs, Energy, InstructionResult, Interpreter};
+use crate::primitives::{Bytes, B176};
+use crate::{Database, EVMData, Inspector};
+use std::time::{Duration, Instant};
+
+/// Limits checked by [LimitsInspector], `None` means unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    /// Maximum number of executed instructions over all call frames.
+    pub max_steps: Option<u64>,
+    /// Maximum wall-clock time, measured from the start of the first call frame.
+    pub max_duration: Option<Duration>,
+    /// Maximum sum of memory sizes of all active call frames, in bytes.
+    pub max_memory: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LimitsInspector {
+    limits: ExecutionLimits,
+    steps: u64,
+    start: Option<Instant>,
+    /// Memory size of active call frames, indexed by depth.
+    frame_memory: Vec<usize>,
+    total_memory: usize,
+    exceeded: Option<InstructionResult>,
+}
+
+impl LimitsInspector {
+    pub fn new(limits: ExecutionLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    pub fn limits(&self) -> &ExecutionLimits {
+        &self.limits
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Limit that halted the execution, if any.
+    pub fn exceeded(&self) -> Option<InstructionResult> {
+        self.exceeded
+    }
+
+    /// Once a limit is exceeded every frame up to the top level call is halted with the same result.
+    fn check(&mut self) -> InstructionResult {
+        if let Some(exceeded) = self.exceeded {
+            return exceeded;
+        }
+        let exceeded = if matches!(self.limits.max_steps, Some(max) if self.steps > max) {
+            InstructionResult::StepLimitExceeded
+        } else if matches!(self.limits.max_memory, Some(max) if self.total_memory > max) {
+            InstructionResult::MemoryBudgetExceeded
+        } else if matches!((self.limits.max_duration, self.start), (Some(max), Some(start)) if start.elapsed() > max)
+        {
+            InstructionResult::TimeLimitExceeded
+        } else {
+            return InstructionResult::Continue;
+        };
+        self.exceeded = Some(exceeded);
+        exceeded
+    }
+
+    /// Record memory size of the frame at `depth`, frames deeper than it have already finished.
+    fn update_memory(&mut self, depth: usize, memory_len: usize) {
+        if self.frame_memory.len() > depth {
+            let finished: usize = self.frame_memory.drain(depth..).sum();
+            self.total_memory -= finished;
+        }
+        self.frame_memory.resize(depth, 0);
+        let current = &mut self.frame_memory[depth - 1];
+        self.total_memory = self.total_memory - *current + memory_len;
+        *current = memory_len;
+    }
+}
+
+impl<DB: Database> Inspector<DB> for LimitsInspector {
+    fn initialize_interp(
+        &mut self,
+        _interp: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+    ) -> InstructionResult {
+        self.start.get_or_insert_with(Instant::now);
+        InstructionResult::Continue
+    }
+
+    fn step(
+        &mut self,
+        _interp: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+    ) -> InstructionResult {
+        self.steps += 1;
+        self.check()
+    }
+
+    fn step_end(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+        _eval: InstructionResult,
+    ) -> InstructionResult {
+        let depth = data.journaled_state.depth() as usize;
+        self.update_memory(depth.max(1), interp.memory.len());
+        self.check()
+    }
+
+    fn call(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &mut CallInputs,
+        _is_static: bool,
+    ) -> (InstructionResult, Energy, Bytes) {
+        (self.check(), Energy::new(0), Bytes::new())
+    }
+
+    fn create(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        (self.check(), None, Energy::new(0), Bytes::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Duration, ExecutionLimits, LimitsInspector};
+    use crate::db::BenchmarkDB;
+    use crate::interpreter::{opcode, InstructionResult};
+    use crate::primitives::{
+        hex_literal::hex, Bytecode, Bytes, ExecutionLimitError, ExecutionResult, Halt, TransactTo,
+        B176,
+    };
+
+    /// Infinite loop that grows memory on each iteration.
+    const MEMORY_LOOP: &[u8] = &[
+        opcode::JUMPDEST,
+        opcode::MSIZE,
+        opcode::MSIZE,
+        opcode::MSTORE,
+        opcode::PUSH1,
+        0x0,
+        opcode::JUMP,
+    ];
+
+    fn evm(code: &[u8]) -> crate::EVM<BenchmarkDB> {
+        let contract_data = Bytes::copy_from_slice(code);
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+        evm.env.tx.energy_limit = 1_000_000_000;
+        evm
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let mut inspector = LimitsInspector::new(ExecutionLimits {
+            max_steps: Some(100),
+            ..Default::default()
+        });
+        let result = evm(MEMORY_LOOP).inspect(&mut inspector).unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::ExecutionLimit(ExecutionLimitError::Steps),
+                ..
+            }
+        ));
+        assert_eq!(inspector.steps(), 101);
+        assert_eq!(
+            inspector.exceeded(),
+            Some(InstructionResult::StepLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut inspector = LimitsInspector::new(ExecutionLimits {
+            max_memory: Some(1024),
+            ..Default::default()
+        });
+        let result = evm(MEMORY_LOOP).inspect(&mut inspector).unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::ExecutionLimit(ExecutionLimitError::Memory),
+                ..
+            }
+        ));
+        // 1024 bytes fit, limit is crossed by the next expansion.
+        assert_eq!(inspector.total_memory, 1056);
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let mut inspector = LimitsInspector::new(ExecutionLimits {
+            max_duration: Some(Duration::from_millis(10)),
+            ..Default::default()
+        });
+        let infinite_loop = [opcode::JUMPDEST, opcode::PUSH1, 0x0, opcode::JUMP];
+        let result = evm(&infinite_loop).inspect(&mut inspector).unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: Halt::ExecutionLimit(ExecutionLimitError::Time),
+                ..
+            }
+        ));
+    }
+}
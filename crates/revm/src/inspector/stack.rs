@@ -0,0 +1,361 @@
+//! Composition of several inspectors into one.
+//!
+//! Inspector is implemented for tuples (up to four elements), slices and `Vec` of inspectors,
+//! so `(EnergyInspector::default(), tracer)` or `Vec<Box<dyn Inspector<DB>>>` can be passed
+//! wherever single inspector is expected.
+//!
+//! Every hook is forwarded to every inspector in order, so each of them sees the full stream of
+//! events even if some other inspector overrides a result. Returned values are merged as:
+//! * `initialize_interp`, `step`, `step_end`: first result that is not [InstructionResult::Continue].
+//! * `call`, `create`: output of the first inspector that does not return [InstructionResult::Continue].
+//! * `call_end`, `create_end`: chained, each inspector receives the output of the previous one
+//!   and the output of the last one is returned.
+//! * `log`, `selfdestruct`: broadcasted.
+
+use crate::evm_impl::EVMData;
+use crate::interpreter::{CallInputs, CreateInputs, Energy, InstructionResult, Interpreter};
+use crate::primitives::{db::Database, Bytes, B176, B256};
+use crate::Inspector;
+use alloc::vec::Vec;
+
+impl<DB: Database, I: Inspector<DB>> Inspector<DB> for [I] {
+    fn initialize_interp(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        first_interrupt(
+            self.iter_mut()
+                .map(|inspector| inspector.initialize_interp(interp, data, is_static)),
+        )
+    }
+
+    fn step(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        first_interrupt(
+            self.iter_mut()
+                .map(|inspector| inspector.step(interp, data, is_static)),
+        )
+    }
+
+    fn log(
+        &mut self,
+        evm_data: &mut EVMData<'_, DB>,
+        address: &B176,
+        topics: &[B256],
+        data: &Bytes,
+    ) {
+        for inspector in self.iter_mut() {
+            inspector.log(evm_data, address, topics, data);
+        }
+    }
+
+    fn step_end(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+        eval: InstructionResult,
+    ) -> InstructionResult {
+        first_interrupt(
+            self.iter_mut()
+                .map(|inspector| inspector.step_end(interp, data, is_static, eval)),
+        )
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        is_static: bool,
+    ) -> (InstructionResult, Energy, Bytes) {
+        self.iter_mut()
+            .map(|inspector| inspector.call(data, inputs, is_static))
+            .fold(None, |first, ret| first.or(overridden(ret.0, ret)))
+            .unwrap_or((InstructionResult::Continue, Energy::new(0), Bytes::new()))
+    }
+
+    fn call_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CallInputs,
+        remaining_energy: Energy,
+        ret: InstructionResult,
+        out: Bytes,
+        is_static: bool,
+    ) -> (InstructionResult, Energy, Bytes) {
+        self.iter_mut().fold(
+            (ret, remaining_energy, out),
+            |(ret, remaining_energy, out), inspector| {
+                inspector.call_end(data, inputs, remaining_energy, ret, out, is_static)
+            },
+        )
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        self.iter_mut()
+            .map(|inspector| inspector.create(data, inputs))
+            .fold(None, |first, ret| first.or(overridden(ret.0, ret)))
+            .unwrap_or((
+                InstructionResult::Continue,
+                None,
+                Energy::new(0),
+                Bytes::default(),
+            ))
+    }
+
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B176>,
+        remaining_energy: Energy,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        self.iter_mut().fold(
+            (ret, address, remaining_energy, out),
+            |(ret, address, remaining_energy, out), inspector| {
+                inspector.create_end(data, inputs, ret, address, remaining_energy, out)
+            },
+        )
+    }
+
+    fn selfdestruct(&mut self, contract: B176, target: B176) {
+        for inspector in self.iter_mut() {
+            inspector.selfdestruct(contract, target);
+        }
+    }
+}
+
+/// Forwards all hooks to the inspector slice.
+macro_rules! forward_inspector_stack {
+    ($this:ident => $slice:expr) => {
+        fn initialize_interp(
+            &mut self,
+            interp: &mut Interpreter,
+            data: &mut EVMData<'_, DB>,
+            is_static: bool,
+        ) -> InstructionResult {
+            let $this = self;
+            $slice.initialize_interp(interp, data, is_static)
+        }
+
+        fn step(
+            &mut self,
+            interp: &mut Interpreter,
+            data: &mut EVMData<'_, DB>,
+            is_static: bool,
+        ) -> InstructionResult {
+            let $this = self;
+            $slice.step(interp, data, is_static)
+        }
+
+        fn log(
+            &mut self,
+            evm_data: &mut EVMData<'_, DB>,
+            address: &B176,
+            topics: &[B256],
+            data: &Bytes,
+        ) {
+            let $this = self;
+            $slice.log(evm_data, address, topics, data)
+        }
+
+        fn step_end(
+            &mut self,
+            interp: &mut Interpreter,
+            data: &mut EVMData<'_, DB>,
+            is_static: bool,
+            eval: InstructionResult,
+        ) -> InstructionResult {
+            let $this = self;
+            $slice.step_end(interp, data, is_static, eval)
+        }
+
+        fn call(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            inputs: &mut CallInputs,
+            is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            let $this = self;
+            $slice.call(data, inputs, is_static)
+        }
+
+        fn call_end(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            inputs: &CallInputs,
+            remaining_energy: Energy,
+            ret: InstructionResult,
+            out: Bytes,
+            is_static: bool,
+        ) -> (InstructionResult, Energy, Bytes) {
+            let $this = self;
+            $slice.call_end(data, inputs, remaining_energy, ret, out, is_static)
+        }
+
+        fn create(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            inputs: &mut CreateInputs,
+        ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+            let $this = self;
+            $slice.create(data, inputs)
+        }
+
+        fn create_end(
+            &mut self,
+            data: &mut EVMData<'_, DB>,
+            inputs: &CreateInputs,
+            ret: InstructionResult,
+            address: Option<B176>,
+            remaining_energy: Energy,
+            out: Bytes,
+        ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+            let $this = self;
+            $slice.create_end(data, inputs, ret, address, remaining_energy, out)
+        }
+
+        fn selfdestruct(&mut self, contract: B176, target: B176) {
+            let $this = self;
+            $slice.selfdestruct(contract, target)
+        }
+    };
+}
+
+impl<DB: Database, I: Inspector<DB>> Inspector<DB> for Vec<I> {
+    forward_inspector_stack!(this => this.as_mut_slice());
+}
+
+macro_rules! impl_inspector_tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<DB: Database, $($ty: Inspector<DB>),+> Inspector<DB> for ($($ty,)+) {
+            forward_inspector_stack!(
+                this => [$(&mut this.$idx as &mut dyn Inspector<DB>),+].as_mut_slice()
+            );
+        }
+    };
+}
+
+impl_inspector_tuple!(A => 0, B => 1);
+impl_inspector_tuple!(A => 0, B => 1, C => 2);
+impl_inspector_tuple!(A => 0, B => 1, C => 2, D => 3);
+
+/// Returns the first result that is not [InstructionResult::Continue], all results are consumed.
+fn first_interrupt(results: impl Iterator<Item = InstructionResult>) -> InstructionResult {
+    results.fold(InstructionResult::Continue, |first, ret| {
+        if first == InstructionResult::Continue {
+            ret
+        } else {
+            first
+        }
+    })
+}
+
+fn overridden<T>(ret: InstructionResult, output: T) -> Option<T> {
+    (ret != InstructionResult::Continue).then_some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::BenchmarkDB;
+    use crate::interpreter::{opcode, InstructionResult, Interpreter};
+    use crate::primitives::{hex_literal::hex, Bytecode, Bytes, TransactTo, B176};
+    use crate::{inspectors::NoOpInspector, Database, EVMData, Inspector};
+
+    /// Counts steps and stops execution after `stop_at` steps if set.
+    #[derive(Default, Debug)]
+    struct CountInspector {
+        steps: usize,
+        step_ends: usize,
+        stop_at: Option<usize>,
+    }
+
+    impl<DB: Database> Inspector<DB> for CountInspector {
+        fn step(
+            &mut self,
+            _interp: &mut Interpreter,
+            _data: &mut EVMData<'_, DB>,
+            _is_static: bool,
+        ) -> InstructionResult {
+            self.steps += 1;
+            if Some(self.steps) == self.stop_at {
+                return InstructionResult::OutOfEnergy;
+            }
+            InstructionResult::Continue
+        }
+
+        fn step_end(
+            &mut self,
+            _interp: &mut Interpreter,
+            _data: &mut EVMData<'_, DB>,
+            _is_static: bool,
+            _eval: InstructionResult,
+        ) -> InstructionResult {
+            self.step_ends += 1;
+            InstructionResult::Continue
+        }
+    }
+
+    fn evm() -> crate::EVM<BenchmarkDB> {
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x1,
+            opcode::ADD,
+            opcode::POP,
+            opcode::STOP,
+        ]);
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+        evm.env.tx.energy_limit = 100_000;
+        evm
+    }
+
+    #[test]
+    fn test_tuple_stack() {
+        let mut evm = evm();
+        let mut first = CountInspector::default();
+        let mut second = CountInspector::default();
+        evm.inspect((&mut first, (NoOpInspector(), &mut second)))
+            .unwrap();
+        assert_eq!(first.steps, 5);
+        assert_eq!(first.step_ends, 5);
+        assert_eq!(second.steps, 5);
+        assert_eq!(second.step_ends, 5);
+    }
+
+    #[test]
+    fn test_vec_stack_interrupt() {
+        let mut evm = evm();
+        let mut counter = CountInspector::default();
+        let mut stopper = CountInspector {
+            stop_at: Some(2),
+            ..Default::default()
+        };
+        let mut stack: Vec<Box<dyn Inspector<BenchmarkDB> + '_>> =
+            vec![Box::new(&mut counter), Box::new(&mut stopper)];
+        let result = evm.inspect(&mut stack).unwrap().result;
+        drop(stack);
+
+        assert!(!result.is_success());
+        // every inspector is called even if other one interrupted the execution.
+        assert_eq!(counter.steps, 2);
+        assert_eq!(counter.step_ends, 1);
+    }
+}
@@ -1,29 +1,32 @@
 //! Inspector that support tracing of EIP-3155 https://eips.ethereum.org/EIPS/eip-3155
 
 use crate::inspectors::EnergyInspector;
-use crate::interpreter::{CallInputs, CreateInputs, Energy, InstructionResult};
-use crate::primitives::{db::Database, hex, Bytes, B176};
+use crate::interpreter::{
+    return_ok, return_revert, CallInputs, CreateInputs, Energy, InstructionResult,
+};
+use crate::primitives::{db::Database, hex, Bytes, PrecompileError, B176};
 use crate::{evm_impl::EVMData, Inspector};
 use revm_interpreter::primitives::U256;
-use revm_interpreter::{opcode, Interpreter, Memory, Stack};
+use revm_interpreter::{opcode, Interpreter, Memory, Stack, STACK_LIMIT};
 use serde_json::json;
 use std::io::Write;
 
 pub struct TracerEip3155 {
     output: Box<dyn Write>,
     energy_inspector: EnergyInspector,
-    #[allow(dead_code)]
+    /// Include the `memory` field in step lines.
     trace_mem: bool,
-    #[allow(dead_code)]
+    /// Include the `returnData` field in step lines.
     trace_return_data: bool,
 
     stack: Stack,
     pc: usize,
     opcode: u8,
     energy: u64,
+    refunded: i64,
     mem_size: usize,
-    #[allow(dead_code)]
     memory: Option<Memory>,
+    return_data: Bytes,
     skip: bool,
 }
 
@@ -38,8 +41,10 @@ impl TracerEip3155 {
             pc: 0,
             opcode: 0,
             energy: 0,
+            refunded: 0,
             mem_size: 0,
             memory: None,
+            return_data: Bytes::new(),
             skip: false,
         }
     }
@@ -71,6 +76,13 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
         self.opcode = interp.current_opcode();
         self.mem_size = interp.memory.len();
         self.energy = self.energy_inspector.energy_remaining();
+        self.refunded = interp.energy.refunded();
+        if self.trace_mem {
+            self.memory = Some(interp.memory.clone());
+        }
+        if self.trace_return_data {
+            self.return_data = interp.return_data_buffer.clone();
+        }
         //
         InstructionResult::Continue
     }
@@ -99,7 +111,10 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
         _inputs: &mut CallInputs,
         _is_static: bool,
     ) -> (InstructionResult, Energy, Bytes) {
-        self.print_log_line(data.journaled_state.depth());
+        // Log the CALL opcode before entering the sub call, the top level call has no caller frame.
+        if data.journaled_state.depth() > 0 {
+            self.print_log_line(data.journaled_state.depth());
+        }
         (InstructionResult::Continue, Energy::new(0), Bytes::new())
     }
 
@@ -114,27 +129,36 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
     ) -> (InstructionResult, Energy, Bytes) {
         self.energy_inspector
             .call_end(data, inputs, remaining_energy, ret, out.clone(), is_static);
-        // self.log_step(interp, data, is_static, eval);
         self.skip = true;
         if data.journaled_state.depth() == 0 {
-            let log_line = json!({
-                //stateroot
-                "output": format!("{out:?}"),
-                "energyUser": format!("0x{:x}", self.energy_inspector.energy_remaining()),
-                //time
-                //fork
-            });
-
-            writeln!(
-                self.output,
-                "{:?}",
-                serde_json::to_string(&log_line).unwrap()
-            )
-            .expect("If output fails we can ignore the logging");
+            let precompile_error = data.precompile_error;
+            self.print_summary(
+                &out,
+                inputs.energy_limit,
+                remaining_energy.remaining(),
+                ret,
+                precompile_error,
+            );
         }
         (ret, remaining_energy, out)
     }
 
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        _inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
+        if data.journaled_state.depth() > 0 {
+            self.print_log_line(data.journaled_state.depth());
+        }
+        (
+            InstructionResult::Continue,
+            None,
+            Energy::new(0),
+            Bytes::default(),
+        )
+    }
+
     fn create_end(
         &mut self,
         data: &mut EVMData<'_, DB>,
@@ -146,6 +170,16 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
     ) -> (InstructionResult, Option<B176>, Energy, Bytes) {
         self.energy_inspector
             .create_end(data, inputs, ret, address, remaining_energy, out.clone());
+        self.skip = true;
+        if data.journaled_state.depth() == 0 {
+            self.print_summary(
+                &out,
+                inputs.energy_limit,
+                remaining_energy.remaining(),
+                ret,
+                None,
+            );
+        }
         (ret, address, remaining_energy, out)
     }
 }
@@ -153,26 +187,168 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
 impl TracerEip3155 {
     fn print_log_line(&mut self, depth: u64) {
         let short_stack: Vec<String> = self.stack.data().iter().map(|&b| short_hex(b)).collect();
-        let log_line = json!({
+        let mut log_line = json!({
             "pc": self.pc,
             "op": self.opcode,
             "energy": format!("0x{:x}", self.energy),
             "energyCost": format!("0x{:x}", self.energy_inspector.last_energy_cost()),
-            //memory?
-            "memSize": self.mem_size,
-            "stack": short_stack,
-            "depth": depth,
-            //returnData
-            //refund
-            "opName": opcode::OPCODE_JUMPMAP[self.opcode as usize],
-            //error
-            //storage
-            //returnStack
         });
+        // Field order follows the go-core json logger, optional fields are omitted when disabled or
+        // empty.
+        let fields = log_line.as_object_mut().unwrap();
+        if let Some(memory) = self.memory.as_ref().filter(|memory| !memory.is_empty()) {
+            fields.insert("memory".into(), json!(hex_bytes(memory.data())));
+        }
+        fields.insert("memSize".into(), json!(self.mem_size));
+        fields.insert("stack".into(), json!(short_stack));
+        if self.trace_return_data && !self.return_data.is_empty() {
+            fields.insert("returnData".into(), json!(hex_bytes(&self.return_data)));
+        }
+        fields.insert("depth".into(), json!(depth));
+        fields.insert("refund".into(), json!(self.refunded));
+        fields.insert(
+            "opName".into(),
+            json!(opcode::OPCODE_JUMPMAP[self.opcode as usize]),
+        );
 
         writeln!(self.output, "{}", serde_json::to_string(&log_line).unwrap())
             .expect("If output fails we can ignore the logging");
     }
+
+    /// Print the final `{"output","energyUsed","error"}` line of the trace.
+    fn print_summary(
+        &mut self,
+        output: &Bytes,
+        energy_limit: u64,
+        remaining_energy: u64,
+        ret: InstructionResult,
+        precompile_error: Option<PrecompileError>,
+    ) {
+        // failed call consumes all its energy, only revert returns the rest.
+        let energy_used = if matches!(ret, return_ok!() | return_revert!()) {
+            energy_limit - remaining_energy
+        } else {
+            energy_limit
+        };
+        let mut log_line = json!({
+            "output": hex::encode(output),
+            "energyUsed": format!("0x{energy_used:x}"),
+        });
+        if !matches!(ret, return_ok!()) {
+            let error = match precompile_error {
+                Some(error) if ret == InstructionResult::PrecompileError => error.to_string(),
+                _ => self.error_message(ret),
+            };
+            log_line
+                .as_object_mut()
+                .unwrap()
+                .insert("error".into(), json!(error));
+        }
+
+        writeln!(self.output, "{}", serde_json::to_string(&log_line).unwrap())
+            .expect("If output fails we can ignore the logging");
+    }
+}
+
+impl TracerEip3155 {
+    /// Error message that go-core prints for the result, the failed instruction is the last
+    /// one that was stepped.
+    fn error_message(&self, ret: InstructionResult) -> String {
+        let message = match ret {
+            InstructionResult::OutOfEnergy
+            | InstructionResult::MemoryOOG
+            | InstructionResult::MemoryLimitOOG
+            | InstructionResult::PrecompileOOG
+            | InstructionResult::InvalidOperandOOG => "out of energy",
+            InstructionResult::Revert => "execution reverted",
+            InstructionResult::CallTooDeep => "max call depth exceeded",
+            InstructionResult::OutOfFund => "insufficient balance for transfer",
+            InstructionResult::CreateCollision => "contract address collision",
+            InstructionResult::CreateContractSizeLimit => "max code size exceeded",
+            InstructionResult::CreateInitcodeSizeLimit => "max initcode size exceeded",
+            InstructionResult::CreateContractStartingWithEF => {
+                "invalid code: must not begin with 0xef"
+            }
+            InstructionResult::InvalidJump => "invalid jump destination",
+            InstructionResult::CallNotAllowedInsideStatic
+            | InstructionResult::StateChangeDuringStaticCall => "write protection",
+            InstructionResult::OutOfOffset => "return data out of bounds",
+            InstructionResult::OverflowPayment => "energy uint64 overflow",
+            InstructionResult::NonceOverflow => "nonce uint64 overflow",
+            InstructionResult::InvalidFEOpcode => "invalid opcode: INVALID",
+            InstructionResult::OpcodeNotFound | InstructionResult::NotActivated => {
+                return match opcode::OPCODE_JUMPMAP[self.opcode as usize] {
+                    Some(name) => format!("invalid opcode: {name}"),
+                    None => format!("invalid opcode: opcode {:#x} not defined", self.opcode),
+                };
+            }
+            InstructionResult::StackUnderflow => {
+                let (inputs, _) = stack_io(self.opcode);
+                return format!("stack underflow ({} <=> {inputs})", self.stack.len());
+            }
+            InstructionResult::StackOverflow => {
+                let (inputs, outputs) = stack_io(self.opcode);
+                let limit = STACK_LIMIT as usize + inputs - outputs;
+                return format!("stack limit reached {} ({limit})", self.stack.len());
+            }
+            InstructionResult::StepLimitExceeded => "step limit exceeded",
+            InstructionResult::TimeLimitExceeded => "time limit exceeded",
+            InstructionResult::MemoryBudgetExceeded => "memory budget exceeded",
+            InstructionResult::PrecompileError => "precompile error",
+            InstructionResult::FatalExternalError => "fatal external error",
+            InstructionResult::Continue
+            | InstructionResult::Stop
+            | InstructionResult::Return
+            | InstructionResult::SelfDestruct
+            | InstructionResult::CallOrCreate
+            | InstructionResult::Suspend => "",
+        };
+        message.to_string()
+    }
+}
+
+/// Number of stack items the opcode takes and pushes.
+fn stack_io(op: u8) -> (usize, usize) {
+    match op {
+        opcode::ADDMOD | opcode::MULMOD => (3, 1),
+        opcode::STOP..=opcode::SAR | opcode::SHA3 => match op {
+            opcode::STOP => (0, 0),
+            opcode::ISZERO | opcode::NOT => (1, 1),
+            _ => (2, 1),
+        },
+        opcode::BALANCE
+        | opcode::CALLDATALOAD
+        | opcode::EXTCODESIZE
+        | opcode::EXTCODEHASH
+        | opcode::BLOCKHASH
+        | opcode::MLOAD
+        | opcode::SLOAD => (1, 1),
+        opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY => (3, 0),
+        opcode::EXTCODECOPY => (4, 0),
+        opcode::POP | opcode::JUMP | opcode::SELFDESTRUCT => (1, 0),
+        opcode::MSTORE
+        | opcode::MSTORE8
+        | opcode::SSTORE
+        | opcode::JUMPI
+        | opcode::RETURN
+        | opcode::REVERT => (2, 0),
+        opcode::JUMPDEST | opcode::INVALID => (0, 0),
+        opcode::DUP1..=opcode::DUP16 => {
+            let n = (op - opcode::DUP1 + 1) as usize;
+            (n, n + 1)
+        }
+        opcode::SWAP1..=opcode::SWAP16 => {
+            let n = (op - opcode::SWAP1 + 2) as usize;
+            (n, n)
+        }
+        opcode::LOG0..=opcode::LOG4 => ((op - opcode::LOG0 + 2) as usize, 0),
+        opcode::CREATE => (3, 1),
+        opcode::CREATE2 => (4, 1),
+        opcode::CALL | opcode::CALLCODE => (7, 1),
+        opcode::DELEGATECALL | opcode::STATICCALL => (6, 1),
+        // environment, block information and pushes.
+        _ => (0, 1),
+    }
 }
 
 fn short_hex(b: U256) -> String {
@@ -185,3 +361,170 @@ fn short_hex(b: U256) -> String {
         format!("0x{s}")
     }
 }
+
+fn hex_bytes(b: &[u8]) -> String {
+    format!("0x{}", hex::encode(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TracerEip3155;
+    use crate::db::BenchmarkDB;
+    use crate::interpreter::opcode;
+    use crate::primitives::{hex_literal::hex, Bytecode, Bytes, TransactTo, B176};
+    use std::cell::RefCell;
+    use std::io::Write;
+    use std::rc::Rc;
+
+    /// Writer whose output stays readable after it is moved into the tracer.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Trace lines of a call to `code`.
+    fn trace(code: Vec<u8>, trace_mem: bool, trace_return_data: bool) -> Vec<String> {
+        let mut evm = crate::new();
+        evm.database(BenchmarkDB::new_bytecode(Bytecode::new_raw(Bytes::from(
+            code,
+        ))));
+        evm.env.tx.caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        evm.env.tx.transact_to =
+            TransactTo::Call(B176(hex!("00000000000000000000000000000000000000000000")));
+        evm.env.tx.energy_limit = 100_000;
+
+        let output = SharedBuffer::default();
+        let mut tracer = TracerEip3155::new(Box::new(output.clone()), trace_mem, trace_return_data);
+        evm.inspect(&mut tracer).unwrap();
+        let output = output.0.borrow();
+        String::from_utf8(output.clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Store 0x2a, copy it with identity precompile and return the copy.
+    fn identity_code() -> Vec<u8> {
+        vec![
+            opcode::PUSH1,
+            0x2a,
+            opcode::PUSH1,
+            0x0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x04,
+            opcode::ENERGY,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x20,
+            opcode::RETURN,
+        ]
+    }
+
+    #[test]
+    fn test_trace_without_memory_and_return_data() {
+        let lines = trace(identity_code(), false, false);
+        assert_eq!(lines.len(), 16);
+        assert_eq!(
+            lines[0],
+            r#"{"pc":0,"op":96,"energy":"0x13498","energyCost":"0x3","memSize":0,"stack":[],"depth":1,"refund":0,"opName":"PUSH1"}"#
+        );
+        assert_eq!(
+            lines[11],
+            r#"{"pc":19,"op":80,"energy":"0x131a7","energyCost":"0x2","memSize":64,"stack":["0x1"],"depth":1,"refund":0,"opName":"POP"}"#
+        );
+        assert_eq!(
+            lines[14],
+            r#"{"pc":24,"op":243,"energy":"0x1319f","energyCost":"0x0","memSize":64,"stack":["0x20","0x20"],"depth":1,"refund":0,"opName":"RETURN"}"#
+        );
+        assert_eq!(
+            lines[15],
+            r#"{"output":"000000000000000000000000000000000000000000000000000000000000002a","energyUsed":"0x2f9"}"#
+        );
+    }
+
+    #[test]
+    fn test_trace_with_memory_and_return_data() {
+        let lines = trace(identity_code(), true, true);
+        assert_eq!(lines.len(), 16);
+        // empty memory is omitted.
+        assert_eq!(
+            lines[0],
+            r#"{"pc":0,"op":96,"energy":"0x13498","energyCost":"0x3","memSize":0,"stack":[],"depth":1,"refund":0,"opName":"PUSH1"}"#
+        );
+        assert_eq!(
+            lines[3],
+            r#"{"pc":5,"op":96,"energy":"0x1348c","energyCost":"0x3","memory":"0x000000000000000000000000000000000000000000000000000000000000002a","memSize":32,"stack":[],"depth":1,"refund":0,"opName":"PUSH1"}"#
+        );
+        assert_eq!(
+            lines[11],
+            concat!(
+                r#"{"pc":19,"op":80,"energy":"0x131a7","energyCost":"0x2","#,
+                r#""memory":"0x000000000000000000000000000000000000000000000000000000000000002a"#,
+                r#"000000000000000000000000000000000000000000000000000000000000002a","#,
+                r#""memSize":64,"stack":["0x1"],"#,
+                r#""returnData":"0x000000000000000000000000000000000000000000000000000000000000002a","#,
+                r#""depth":1,"refund":0,"opName":"POP"}"#
+            )
+        );
+        assert_eq!(
+            lines[15],
+            r#"{"output":"000000000000000000000000000000000000000000000000000000000000002a","energyUsed":"0x2f9"}"#
+        );
+    }
+
+    #[test]
+    fn test_trace_error_summary() {
+        // jump to an offset that is not JUMPDEST, failed call uses all its energy.
+        let lines = trace(vec![opcode::PUSH1, 0x5, opcode::JUMP], false, false);
+        assert_eq!(
+            lines.last().unwrap(),
+            r#"{"output":"","energyUsed":"0x13498","error":"invalid jump destination"}"#
+        );
+
+        let lines = trace(vec![opcode::PUSH1, 0x1, opcode::ADD], false, false);
+        assert_eq!(
+            lines.last().unwrap(),
+            r#"{"output":"","energyUsed":"0x13498","error":"stack underflow (1 <=> 2)"}"#
+        );
+
+        let lines = trace(vec![0x0c], false, false);
+        assert_eq!(
+            lines.last().unwrap(),
+            r#"{"output":"","energyUsed":"0x13498","error":"invalid opcode: opcode 0xc not defined"}"#
+        );
+
+        // revert returns energy that is left.
+        let lines = trace(
+            vec![opcode::PUSH1, 0x0, opcode::PUSH1, 0x0, opcode::REVERT],
+            false,
+            false,
+        );
+        assert_eq!(
+            lines.last().unwrap(),
+            r#"{"output":"","energyUsed":"0x6","error":"execution reverted"}"#
+        );
+    }
+}
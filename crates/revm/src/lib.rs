@@ -1,10 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod bytecode_cache;
 pub mod db;
 mod evm;
 mod evm_impl;
 mod inspector;
 mod journaled_state;
+mod lru;
+pub mod resumable;
 
 #[cfg(all(feature = "with-serde", not(feature = "serde")))]
 compile_error!("`with-serde` feature has been renamed to `serde`.");
@@ -12,8 +15,11 @@ compile_error!("`with-serde` feature has been renamed to `serde`.");
 pub(crate) const USE_ENERGY: bool = !cfg!(feature = "no_energy_measuring");
 pub type DummyStateDB = InMemoryDB;
 
+pub use bytecode_cache::{BytecodeCache, BytecodeCacheStats};
 pub use db::{Database, DatabaseCommit, InMemoryDB};
-pub use evm::{evm_inner, new, EVM};
+pub use evm::{
+    evm_inner, evm_inner_resumable, evm_inner_resumable_send, evm_inner_with_cache, new, EVM,
+};
 pub use evm_impl::EVMData;
 pub use journaled_state::{JournalEntry, JournaledState};
 
@@ -0,0 +1,120 @@
+//! Least recently used eviction order shared by size bounded caches.
+use crate::primitives::HashMap;
+use alloc::collections::BTreeMap;
+use core::hash::Hash;
+
+/// Recency order and total size of evictable cache entries. Values are kept by the cache, which
+/// removes them when their keys are evicted.
+#[derive(Clone, Debug)]
+pub(crate) struct LruIndex<K> {
+    /// Maximum sum of entry sizes.
+    capacity: usize,
+    size: usize,
+    /// Incremented on every access, used to order entries by recency.
+    tick: u64,
+    /// Key to its access tick and size.
+    entries: HashMap<K, (u64, usize)>,
+    /// Access tick to key, first entry is least recently used.
+    recency: BTreeMap<u64, K>,
+}
+
+impl<K: Copy + Eq + Hash> LruIndex<K> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            size: 0,
+            tick: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sum of entry sizes.
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Mark entry as most recently used, returns false if key is not in the index.
+    pub(crate) fn touch(&mut self, key: &K) -> bool {
+        let tick = self.next_tick();
+        let Some((last_used, _)) = self.entries.get_mut(key) else {
+            return false;
+        };
+        self.recency.remove(last_used);
+        self.recency.insert(tick, *key);
+        *last_used = tick;
+        true
+    }
+
+    /// Add entry as most recently used, calling `evict` with each least recently used key removed
+    /// to make room for it. Returns false, without evicting anything, if entry is larger than the
+    /// capacity and can't be added.
+    pub(crate) fn insert(&mut self, key: K, size: usize, mut evict: impl FnMut(K)) -> bool {
+        if size > self.capacity {
+            return false;
+        }
+        self.remove(&key);
+        while self.size + size > self.capacity {
+            match self.recency.pop_first() {
+                Some((_, evicted)) => {
+                    if let Some((_, evicted_size)) = self.entries.remove(&evicted) {
+                        self.size -= evicted_size;
+                    }
+                    evict(evicted);
+                }
+                None => break,
+            }
+        }
+        let tick = self.next_tick();
+        self.recency.insert(tick, key);
+        self.entries.insert(key, (tick, size));
+        self.size += size;
+        true
+    }
+
+    /// Remove entry, returns its size if key was in the index.
+    pub(crate) fn remove(&mut self, key: &K) -> Option<usize> {
+        let (last_used, size) = self.entries.remove(key)?;
+        self.recency.remove(&last_used);
+        self.size -= size;
+        Some(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LruIndex;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let mut lru = LruIndex::new(10);
+        let mut evicted = Vec::new();
+        assert!(lru.insert(1, 4, |key| evicted.push(key)));
+        assert!(lru.insert(2, 4, |key| evicted.push(key)));
+        // touch first so second is evicted.
+        assert!(lru.touch(&1));
+        assert!(lru.insert(3, 4, |key| evicted.push(key)));
+        assert_eq!(evicted, [2]);
+        assert_eq!((lru.len(), lru.size()), (2, 8));
+
+        assert!(!lru.insert(4, 11, |key| evicted.push(key)));
+        assert_eq!(lru.remove(&1), Some(4));
+        assert!(!lru.touch(&1));
+        assert_eq!((lru.len(), lru.size()), (1, 4));
+    }
+}
@@ -0,0 +1,213 @@
+//! Execution that suspends when database can't provide data instead of failing.
+//!
+//! Before executing an instruction or entering a call frame that loads state, resumable execution
+//! asks the database for it. If database returns an error, execution is suspended with
+//! [ResumeResult::NeedsData] and the interpreter state is kept as is. Caller can obtain the data
+//! (e.g. from a remote node, asynchronously), insert it into the database available
+//! with [ResumableTransact::db] and call [ResumableTransact::resume] to continue from the same point.
+//!
+//! Database is expected to cache what it returns, [crate::db::CacheDB] over an external database
+//! that returns an error for data that is not available locally is a good fit.
+use crate::db::Database;
+use crate::primitives::{EVMError, ResultAndState, B176, B256, U256};
+
+/// State that execution needs and database was not able to provide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataRequest {
+    /// Basic account information, see [Database::basic].
+    Account(B176),
+    /// Storage slot of the account, see [Database::storage].
+    Storage(B176, U256),
+    /// Bytecode by its hash, see [Database::code_by_hash].
+    Code(B256),
+    /// Hash of the block by its number, see [Database::block_hash].
+    BlockHash(U256),
+}
+
+/// Outcome of [ResumableTransact::resume].
+#[derive(Debug)]
+pub enum ResumeResult<DBError> {
+    /// Transaction finished.
+    Done(ResultAndState),
+    /// Execution is suspended until database can provide `request`.
+    /// `error` is what database returned when it was asked for it.
+    NeedsData {
+        request: DataRequest,
+        error: DBError,
+    },
+}
+
+pub trait ResumableTransact<DB: Database> {
+    /// Run transaction until it finishes or database fails to provide data.
+    ///
+    /// # Panics
+    ///
+    /// If called after transaction finished or failed.
+    fn resume(&mut self) -> Result<ResumeResult<DB::Error>, EVMError<DB::Error>>;
+
+    /// Database used by the execution, requested data should be inserted here before resuming.
+    fn db(&mut self) -> &mut DB;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataRequest, ResumeResult};
+    use crate::db::{CacheDB, DatabaseRef, DbAccount, InMemoryDB};
+    use crate::interpreter::opcode;
+    use crate::primitives::{
+        hex_literal::hex, AccountInfo, Bytecode, Bytes, TransactTo, B176, B256, U256,
+    };
+    use crate::Database;
+
+    /// Database without any local data.
+    struct Offline;
+
+    impl DatabaseRef for Offline {
+        type Error = ();
+
+        fn basic(&self, _address: B176) -> Result<Option<AccountInfo>, Self::Error> {
+            Err(())
+        }
+
+        fn code_by_hash(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Err(())
+        }
+
+        fn storage(&self, _address: B176, _index: U256) -> Result<U256, Self::Error> {
+            Err(())
+        }
+
+        fn block_hash(&self, _number: U256) -> Result<B256, Self::Error> {
+            Err(())
+        }
+    }
+
+    /// Copy requested data from `remote`, code is supplied separately from account.
+    fn supply(local: &mut CacheDB<Offline>, remote: &mut InMemoryDB, request: &DataRequest) {
+        match *request {
+            DataRequest::Account(address) => match remote.basic(address).unwrap() {
+                Some(mut info) => {
+                    info.code = None;
+                    local.insert_account_info(address, info);
+                }
+                None => {
+                    local
+                        .accounts
+                        .insert(address, DbAccount::new_not_existing());
+                }
+            },
+            DataRequest::Storage(address, index) => {
+                let value = remote.storage(address, index).unwrap();
+                local.insert_account_storage(address, index, value).unwrap();
+            }
+            DataRequest::Code(hash) => {
+                local
+                    .contracts
+                    .insert(hash, remote.code_by_hash(hash).unwrap());
+            }
+            DataRequest::BlockHash(number) => {
+                local
+                    .block_hashes
+                    .insert(number, remote.block_hash(number).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_resume_with_supplied_data() {
+        let caller = B176(hex!("10000000000000000000000000000000000000000000"));
+        let contract = B176(hex!("00000000000000000000000000000000000000000100"));
+        let callee = B176(hex!("00000000000000000000000000000000000000000200"));
+        let rich = B176(hex!("00000000000000000000000000000000000000000007"));
+
+        let contract_code = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::POP,
+            opcode::PUSH1,
+            0x7,
+            opcode::BALANCE,
+            opcode::POP,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH2,
+            0x2,
+            0x0,
+            opcode::ENERGY,
+            opcode::CALL,
+            opcode::STOP,
+        ]);
+        let callee_code = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x2,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+
+        let mut remote = InMemoryDB::default();
+        remote.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+        remote.insert_account_info(rich, AccountInfo::from_balance(U256::from(100)));
+        for (address, code, slot) in [(contract, contract_code, 5), (callee, callee_code, 9)] {
+            let info = AccountInfo::new(U256::ZERO, 1, Bytecode::new_raw(code));
+            remote.insert_account_info(address, info);
+            remote
+                .insert_account_storage(address, U256::from(1), U256::from(slot))
+                .unwrap();
+        }
+
+        let mut evm = crate::new();
+        evm.env.tx.caller = caller;
+        evm.env.tx.transact_to = TransactTo::Call(contract);
+        evm.env.tx.energy_limit = 100_000;
+
+        evm.database(remote.clone());
+        let expected = evm.transact().unwrap();
+
+        let mut evm = crate::EVM {
+            env: evm.env,
+            db: Some(CacheDB::new(Offline)),
+            bytecode_cache: None,
+        };
+        let mut execution = evm.transact_resumable();
+        let mut requests = Vec::new();
+        let result = loop {
+            match execution.resume().unwrap() {
+                ResumeResult::Done(result) => break result,
+                ResumeResult::NeedsData { request, .. } => {
+                    supply(execution.db(), &mut remote, &request);
+                    requests.push(request);
+                }
+            }
+        };
+
+        assert_eq!(result, expected);
+        assert!(result.result.is_success());
+        for request in [
+            DataRequest::Account(caller),
+            DataRequest::Account(contract),
+            DataRequest::Storage(contract, U256::from(1)),
+            DataRequest::Account(rich),
+            DataRequest::Account(callee),
+            DataRequest::Storage(callee, U256::from(1)),
+            DataRequest::Storage(callee, U256::from(2)),
+        ] {
+            assert!(requests.contains(&request), "missing {request:?}");
+        }
+        assert!(requests
+            .iter()
+            .any(|request| matches!(request, DataRequest::Code(_))));
+    }
+}